@@ -0,0 +1,44 @@
+#![allow(dead_code)]
+
+use criterion::Criterion;
+
+use advent_of_code_2015::solutions::Solution;
+use advent_of_code_2015::util::Input;
+
+/// Benchmarks parsing and both parts of a day separately, skipping days without an input.
+pub fn bench<S: Solution>(c: &mut Criterion) {
+    bench_with::<S>(c, 100);
+}
+
+/// Same as [`bench`], but with the minimal amount of samples for the slow brute force days.
+pub fn bench_slow<S: Solution>(c: &mut Criterion) {
+    bench_with::<S>(c, 10);
+}
+
+fn bench_with<S: Solution>(c: &mut Criterion, sample_size: usize) {
+    let input = match Input::load(S::DAY) {
+        Ok(input) => input.text(),
+        Err(e) => {
+            eprintln!("Skipping benchmark: {e}");
+            return;
+        }
+    };
+    let parsed = S::parse(input).unwrap();
+
+    let mut group = c.benchmark_group(format!("day{:0>2}", S::DAY));
+    group.sample_size(sample_size);
+
+    group.bench_function("parse", |b| {
+        b.iter(|| S::parse(input));
+    });
+
+    group.bench_function("part1", |b| {
+        b.iter(|| S::part_1(&parsed));
+    });
+
+    group.bench_function("part2", |b| {
+        b.iter(|| S::part_2(&parsed));
+    });
+
+    group.finish();
+}
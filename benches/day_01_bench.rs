@@ -2,21 +2,14 @@ use criterion::{criterion_group, criterion_main, Criterion};
 
 use advent_of_code_2015::solutions::*;
 
+mod common;
+
 /// Run this benchmark using
 /// ```shell
-/// $ cargo bench
+/// $ cargo bench --bench day_01_bench
 /// ```
 fn day01(c: &mut Criterion) {
-    let input = include_str!("../inputs/day_01.txt").trim();
-    let mut group = c.benchmark_group("day01");
-
-    group.bench_function("part1", |b| {
-        b.iter(|| day_01::solve_1(input));
-    });
-
-    group.bench_function("part2", |b| {
-        b.iter(|| day_01::solve_2(input));
-    });
+    common::bench::<day_01::Day01>(c);
 }
 
 criterion_group!(benches, day01);
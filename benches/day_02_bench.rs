@@ -0,0 +1,16 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use advent_of_code_2015::solutions::*;
+
+mod common;
+
+/// Run this benchmark using
+/// ```shell
+/// $ cargo bench --bench day_02_bench
+/// ```
+fn day02(c: &mut Criterion) {
+    common::bench::<day_02::Day02>(c);
+}
+
+criterion_group!(benches, day02);
+criterion_main!(benches);
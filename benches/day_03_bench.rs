@@ -0,0 +1,16 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use advent_of_code_2015::solutions::*;
+
+mod common;
+
+/// Run this benchmark using
+/// ```shell
+/// $ cargo bench --bench day_03_bench
+/// ```
+fn day03(c: &mut Criterion) {
+    common::bench::<day_03::Day03>(c);
+}
+
+criterion_group!(benches, day03);
+criterion_main!(benches);
@@ -0,0 +1,16 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use advent_of_code_2015::solutions::*;
+
+mod common;
+
+/// Run this benchmark using
+/// ```shell
+/// $ cargo bench --bench day_04_bench
+/// ```
+fn day04(c: &mut Criterion) {
+    common::bench_slow::<day_04::Day04>(c);
+}
+
+criterion_group!(benches, day04);
+criterion_main!(benches);
@@ -0,0 +1,16 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use advent_of_code_2015::solutions::*;
+
+mod common;
+
+/// Run this benchmark using
+/// ```shell
+/// $ cargo bench --bench day_05_bench
+/// ```
+fn day05(c: &mut Criterion) {
+    common::bench::<day_05::Day05>(c);
+}
+
+criterion_group!(benches, day05);
+criterion_main!(benches);
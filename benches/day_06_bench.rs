@@ -0,0 +1,16 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use advent_of_code_2015::solutions::*;
+
+mod common;
+
+/// Run this benchmark using
+/// ```shell
+/// $ cargo bench --bench day_06_bench
+/// ```
+fn day06(c: &mut Criterion) {
+    common::bench::<day_06::Day06>(c);
+}
+
+criterion_group!(benches, day06);
+criterion_main!(benches);
@@ -0,0 +1,16 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use advent_of_code_2015::solutions::*;
+
+mod common;
+
+/// Run this benchmark using
+/// ```shell
+/// $ cargo bench --bench day_07_bench
+/// ```
+fn day07(c: &mut Criterion) {
+    common::bench::<day_07::Day07>(c);
+}
+
+criterion_group!(benches, day07);
+criterion_main!(benches);
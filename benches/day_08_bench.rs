@@ -0,0 +1,16 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use advent_of_code_2015::solutions::*;
+
+mod common;
+
+/// Run this benchmark using
+/// ```shell
+/// $ cargo bench --bench day_08_bench
+/// ```
+fn day08(c: &mut Criterion) {
+    common::bench::<day_08::Day08>(c);
+}
+
+criterion_group!(benches, day08);
+criterion_main!(benches);
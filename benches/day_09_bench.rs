@@ -0,0 +1,16 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use advent_of_code_2015::solutions::*;
+
+mod common;
+
+/// Run this benchmark using
+/// ```shell
+/// $ cargo bench --bench day_09_bench
+/// ```
+fn day09(c: &mut Criterion) {
+    common::bench::<day_09::Day09>(c);
+}
+
+criterion_group!(benches, day09);
+criterion_main!(benches);
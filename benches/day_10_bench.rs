@@ -0,0 +1,16 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use advent_of_code_2015::solutions::*;
+
+mod common;
+
+/// Run this benchmark using
+/// ```shell
+/// $ cargo bench --bench day_10_bench
+/// ```
+fn day10(c: &mut Criterion) {
+    common::bench::<day_10::Day10>(c);
+}
+
+criterion_group!(benches, day10);
+criterion_main!(benches);
@@ -0,0 +1,16 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use advent_of_code_2015::solutions::*;
+
+mod common;
+
+/// Run this benchmark using
+/// ```shell
+/// $ cargo bench --bench day_11_bench
+/// ```
+fn day11(c: &mut Criterion) {
+    common::bench::<day_11::Day11>(c);
+}
+
+criterion_group!(benches, day11);
+criterion_main!(benches);
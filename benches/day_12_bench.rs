@@ -0,0 +1,16 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use advent_of_code_2015::solutions::*;
+
+mod common;
+
+/// Run this benchmark using
+/// ```shell
+/// $ cargo bench --bench day_12_bench
+/// ```
+fn day12(c: &mut Criterion) {
+    common::bench::<day_12::Day12>(c);
+}
+
+criterion_group!(benches, day12);
+criterion_main!(benches);
@@ -0,0 +1,16 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use advent_of_code_2015::solutions::*;
+
+mod common;
+
+/// Run this benchmark using
+/// ```shell
+/// $ cargo bench --bench day_13_bench
+/// ```
+fn day13(c: &mut Criterion) {
+    common::bench::<day_13::Day13>(c);
+}
+
+criterion_group!(benches, day13);
+criterion_main!(benches);
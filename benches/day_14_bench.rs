@@ -0,0 +1,16 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use advent_of_code_2015::solutions::*;
+
+mod common;
+
+/// Run this benchmark using
+/// ```shell
+/// $ cargo bench --bench day_14_bench
+/// ```
+fn day14(c: &mut Criterion) {
+    common::bench::<day_14::Day14>(c);
+}
+
+criterion_group!(benches, day14);
+criterion_main!(benches);
@@ -0,0 +1,16 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use advent_of_code_2015::solutions::*;
+
+mod common;
+
+/// Run this benchmark using
+/// ```shell
+/// $ cargo bench --bench day_15_bench
+/// ```
+fn day15(c: &mut Criterion) {
+    common::bench::<day_15::Day15>(c);
+}
+
+criterion_group!(benches, day15);
+criterion_main!(benches);
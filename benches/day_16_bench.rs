@@ -0,0 +1,16 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use advent_of_code_2015::solutions::*;
+
+mod common;
+
+/// Run this benchmark using
+/// ```shell
+/// $ cargo bench --bench day_16_bench
+/// ```
+fn day16(c: &mut Criterion) {
+    common::bench::<day_16::Day16>(c);
+}
+
+criterion_group!(benches, day16);
+criterion_main!(benches);
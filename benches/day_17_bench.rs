@@ -0,0 +1,16 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use advent_of_code_2015::solutions::*;
+
+mod common;
+
+/// Run this benchmark using
+/// ```shell
+/// $ cargo bench --bench day_17_bench
+/// ```
+fn day17(c: &mut Criterion) {
+    common::bench::<day_17::Day17>(c);
+}
+
+criterion_group!(benches, day17);
+criterion_main!(benches);
@@ -0,0 +1,16 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use advent_of_code_2015::solutions::*;
+
+mod common;
+
+/// Run this benchmark using
+/// ```shell
+/// $ cargo bench --bench day_18_bench
+/// ```
+fn day18(c: &mut Criterion) {
+    common::bench::<day_18::Day18>(c);
+}
+
+criterion_group!(benches, day18);
+criterion_main!(benches);
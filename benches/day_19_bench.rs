@@ -0,0 +1,16 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use advent_of_code_2015::solutions::*;
+
+mod common;
+
+/// Run this benchmark using
+/// ```shell
+/// $ cargo bench --bench day_19_bench
+/// ```
+fn day19(c: &mut Criterion) {
+    common::bench::<day_19::Day19>(c);
+}
+
+criterion_group!(benches, day19);
+criterion_main!(benches);
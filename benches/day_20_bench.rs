@@ -0,0 +1,16 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use advent_of_code_2015::solutions::*;
+
+mod common;
+
+/// Run this benchmark using
+/// ```shell
+/// $ cargo bench --bench day_20_bench
+/// ```
+fn day20(c: &mut Criterion) {
+    common::bench_slow::<day_20::Day20>(c);
+}
+
+criterion_group!(benches, day20);
+criterion_main!(benches);
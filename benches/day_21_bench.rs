@@ -0,0 +1,16 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use advent_of_code_2015::solutions::*;
+
+mod common;
+
+/// Run this benchmark using
+/// ```shell
+/// $ cargo bench --bench day_21_bench
+/// ```
+fn day21(c: &mut Criterion) {
+    common::bench::<day_21::Day21>(c);
+}
+
+criterion_group!(benches, day21);
+criterion_main!(benches);
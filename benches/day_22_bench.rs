@@ -0,0 +1,16 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use advent_of_code_2015::solutions::*;
+
+mod common;
+
+/// Run this benchmark using
+/// ```shell
+/// $ cargo bench --bench day_22_bench
+/// ```
+fn day22(c: &mut Criterion) {
+    common::bench_slow::<day_22::Day22>(c);
+}
+
+criterion_group!(benches, day22);
+criterion_main!(benches);
@@ -0,0 +1,16 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use advent_of_code_2015::solutions::*;
+
+mod common;
+
+/// Run this benchmark using
+/// ```shell
+/// $ cargo bench --bench day_23_bench
+/// ```
+fn day23(c: &mut Criterion) {
+    common::bench::<day_23::Day23>(c);
+}
+
+criterion_group!(benches, day23);
+criterion_main!(benches);
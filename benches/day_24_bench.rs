@@ -0,0 +1,16 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use advent_of_code_2015::solutions::*;
+
+mod common;
+
+/// Run this benchmark using
+/// ```shell
+/// $ cargo bench --bench day_24_bench
+/// ```
+fn day24(c: &mut Criterion) {
+    common::bench_slow::<day_24::Day24>(c);
+}
+
+criterion_group!(benches, day24);
+criterion_main!(benches);
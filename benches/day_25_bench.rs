@@ -0,0 +1,16 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use advent_of_code_2015::solutions::*;
+
+mod common;
+
+/// Run this benchmark using
+/// ```shell
+/// $ cargo bench --bench day_25_bench
+/// ```
+fn day25(c: &mut Criterion) {
+    common::bench::<day_25::Day25>(c);
+}
+
+criterion_group!(benches, day25);
+criterion_main!(benches);
@@ -0,0 +1,179 @@
+use std::convert::Infallible;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use crate::error::{InputError, ParseError};
+
+/// The answer to a part of a day, either a number or a piece of text.
+///
+/// Numbers compare by value, so `46065`, `46_065` and `046065` are all the same answer.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum Answer {
+    Number(i128),
+    Text(String),
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Number(n) => write!(f, "{n}"),
+            Answer::Text(t) => write!(f, "{t}"),
+        }
+    }
+}
+
+impl FromStr for Answer {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+
+        Ok(match i128::from_str(&s.replace('_', "")) {
+            Ok(n) => Answer::Number(n),
+            Err(_) => Answer::Text(s.to_string()),
+        })
+    }
+}
+
+macro_rules! number_answer {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Self {
+                    Answer::Number(n as i128)
+                }
+            }
+        )*
+    };
+}
+
+number_answer!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, usize);
+
+impl From<u128> for Answer {
+    fn from(n: u128) -> Self {
+        match i128::try_from(n) {
+            Ok(n) => Answer::Number(n),
+            Err(_) => Answer::Text(n.to_string()),
+        }
+    }
+}
+
+impl From<String> for Answer {
+    fn from(text: String) -> Self {
+        Answer::Text(text)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(text: &str) -> Self {
+        Answer::Text(text.to_string())
+    }
+}
+
+/// Conversion of what a part of a day returns into its [`Answer`].
+///
+/// Parts that cannot answer every valid input return a `Result`, reporting why as a [`ParseError`].
+pub trait IntoAnswer {
+    fn into_answer(self) -> Result<Answer, ParseError>;
+}
+
+impl<T: Into<Answer>> IntoAnswer for T {
+    fn into_answer(self) -> Result<Answer, ParseError> {
+        Ok(self.into())
+    }
+}
+
+impl<T: Into<Answer>> IntoAnswer for Result<T, ParseError> {
+    fn into_answer(self) -> Result<Answer, ParseError> {
+        self.map(Into::into)
+    }
+}
+
+/// The known answers of a day, read from `answers/day_XX.txt`.
+///
+/// The first line holds the answer to part 1, the second line the answer to part 2.
+/// An empty or missing line means the answer is not known (yet).
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct Expected {
+    pub part_1: Option<Answer>,
+    pub part_2: Option<Answer>,
+}
+
+impl Expected {
+    pub fn new(raw: &str) -> Self {
+        let mut answers = raw.lines().map(|line| match line.trim() {
+            "" => None,
+            line => Some(Answer::from_str(line).unwrap()),
+        });
+
+        Self {
+            part_1: answers.next().flatten(),
+            part_2: answers.next().flatten(),
+        }
+    }
+
+    /// Loads the known answers of `day`, a missing file simply means nothing is known.
+    pub fn load(day: u8) -> Result<Self, InputError> {
+        Self::from_path(day, &Self::path(day))
+    }
+
+    pub fn from_path(day: u8, path: &Path) -> Result<Self, InputError> {
+        match fs::read_to_string(path) {
+            Ok(raw) => Ok(Self::new(&raw)),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(InputError::Unreadable {
+                day,
+                path: path.to_path_buf(),
+                reason: e.to_string(),
+            }),
+        }
+    }
+
+    pub fn path(day: u8) -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("answers")
+            .join(format!("day_{:0>2}.txt", day))
+    }
+
+    pub fn part(&self, part: u8) -> Option<&Answer> {
+        match part {
+            1 => self.part_1.as_ref(),
+            2 => self.part_2.as_ref(),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn answers_compare_by_value() {
+        assert_eq!(
+            Answer::from(46_065_u16),
+            Answer::from_str("46_065").unwrap()
+        );
+        assert_eq!(Answer::from(-1_i32), Answer::from_str(" -1 ").unwrap());
+        assert_eq!(
+            Answer::from(u128::MAX),
+            Answer::from_str(&u128::MAX.to_string()).unwrap()
+        );
+        assert_eq!(
+            Answer::from("hepxxyzz"),
+            Answer::from_str("hepxxyzz").unwrap()
+        );
+    }
+
+    #[test]
+    fn expected_answers_are_optional() {
+        let expected = Expected::new("\n14_110_788\n");
+
+        assert_eq!(None, expected.part(1));
+        assert_eq!(Some(&Answer::Number(14_110_788)), expected.part(2));
+        assert_eq!(Expected::default(), Expected::load(0).unwrap());
+    }
+}
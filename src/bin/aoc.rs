@@ -0,0 +1,319 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::ops::Not;
+use std::ops::RangeInclusive;
+use std::path::Path;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+use std::{env, process};
+
+use itertools::Itertools;
+
+use advent_of_code_2015::answer::{Answer, Expected};
+use advent_of_code_2015::error::{InputError, ParseError};
+use advent_of_code_2015::solutions::{registry, Day};
+use advent_of_code_2015::util::Input;
+
+/// Binary to run the solutions of one or more Advent of Code days.
+///
+/// # How to run
+///
+/// This will run part 2 of "Day 07" against the given input file:
+/// ```shell
+/// $ cargo run --release --bin aoc -- run --day 7 --part 2 --input inputs/day_07.txt
+/// ```
+///
+/// Leaving out `--part` runs both parts, leaving out `--input` reads `inputs/day_XX.txt`:
+/// ```shell
+/// $ cargo run --release --bin aoc -- run --day 7
+/// ```
+///
+/// This will run both parts of every day in sequence, followed by a summary table:
+/// ```shell
+/// $ cargo run --release --bin aoc -- run --all
+/// ```
+///
+/// Replacing `run` by `verify` checks every answer against the known answers in `answers/day_XX.txt`,
+/// holding the answer to part 1 on the first line and the answer to part 2 on the second line.
+/// Unknown answers are reported but not counted as a failure, wrong answers are.
+/// Days without an input are skipped when running all days:
+/// ```shell
+/// $ cargo run --release --bin aoc -- verify --all
+/// ```
+fn main() {
+    let args: Vec<String> = env::args().collect();
+
+    let config = Config::build(&args).unwrap_or_else(|err| {
+        eprintln!("Problem parsing arguments: {err}");
+        eprintln!("{USAGE}");
+        process::exit(1);
+    });
+
+    if let Err(e) = run(config) {
+        eprintln!("Application error: {e}");
+        process::exit(1);
+    }
+}
+
+const USAGE: &str = "\
+Usage: aoc <run|verify> --day <1-25> [--part <1|2>] [--input <path>]
+       aoc <run|verify> --all";
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum Command {
+    Run,
+    Verify,
+}
+
+#[derive(Debug)]
+struct Config {
+    command: Command,
+    days: Vec<u8>,
+    parts: Vec<u8>,
+    input: Option<String>,
+}
+
+impl Config {
+    fn build(args: &[String]) -> Result<Config, String> {
+        let command = match args.get(1).map(|s| s.as_str()) {
+            Some("run") => Command::Run,
+            Some("verify") => Command::Verify,
+            Some(command) => return Err(format!("unknown command '{command}'")),
+            None => return Err("missing command".to_string()),
+        };
+
+        let mut day = None;
+        let mut part = None;
+        let mut input = None;
+        let mut all = false;
+
+        let mut args = args[2..].iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--all" => all = true,
+                "--day" => day = Some(Self::number(args.next(), "--day", 1..=25)?),
+                "--part" => part = Some(Self::number(args.next(), "--part", 1..=2)?),
+                "--input" => {
+                    input = Some(
+                        args.next()
+                            .ok_or("missing value for '--input'")?
+                            .to_string(),
+                    )
+                }
+                _ => return Err(format!("unknown argument '{arg}'")),
+            }
+        }
+
+        let days = match (all, day) {
+            (true, None) if input.is_none() => (1..=25).collect(),
+            (true, None) => return Err("'--input' cannot be combined with '--all'".to_string()),
+            (true, Some(_)) => return Err("'--day' cannot be combined with '--all'".to_string()),
+            (false, Some(day)) => vec![day],
+            (false, None) => return Err("either '--day' or '--all' is required".to_string()),
+        };
+        let parts = part.map_or_else(|| vec![1, 2], |p| vec![p]);
+
+        Ok(Config {
+            command,
+            days,
+            parts,
+            input,
+        })
+    }
+
+    fn number(value: Option<&String>, flag: &str, range: RangeInclusive<u8>) -> Result<u8, String> {
+        let value = value.ok_or(format!("missing value for '{flag}'"))?;
+
+        u8::from_str(value)
+            .ok()
+            .filter(|n| range.contains(n))
+            .ok_or(format!(
+                "invalid value '{value}' for '{flag}', expected {}-{}",
+                range.start(),
+                range.end()
+            ))
+    }
+}
+
+#[derive(Debug)]
+struct Outcome {
+    day: u8,
+    part: u8,
+    answer: Answer,
+    elapsed: Duration,
+    verdict: Option<Verdict>,
+}
+
+/// How an answer compares to the known answer of its day and part.
+#[derive(Debug, Clone, Eq, PartialEq)]
+enum Verdict {
+    Correct,
+    Wrong(Answer),
+    Unknown,
+}
+
+impl Verdict {
+    fn of(answer: &Answer, expected: Option<&Answer>) -> Self {
+        match expected {
+            Some(expected) if expected == answer => Verdict::Correct,
+            Some(expected) => Verdict::Wrong(expected.clone()),
+            None => Verdict::Unknown,
+        }
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "correct"),
+            Verdict::Wrong(expected) => write!(f, "WRONG, expected {expected}"),
+            Verdict::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+fn run(config: Config) -> Result<(), Box<dyn Error>> {
+    let registry = registry();
+    let mut outcomes = vec![];
+    let mut skipped = vec![];
+
+    for day in &config.days {
+        let solution = registry
+            .get(day)
+            .ok_or(format!("day {day} is not solved yet"))?;
+        let input = match &config.input {
+            Some(path) => Input::from_path(*day, Path::new(path))?,
+            None => match Input::load(*day) {
+                Ok(input) => input.clone(),
+                // Running all days only covers the inputs that are there
+                Err(e @ InputError::Missing { .. }) if config.days.len() > 1 => {
+                    println!("Day {day:0>2} skipped: {e}");
+                    skipped.push(*day);
+                    continue;
+                }
+                Err(e) => return Err(e.into()),
+            },
+        };
+        let expected = match config.command {
+            Command::Run => None,
+            Command::Verify => Some(Expected::load(*day)?),
+        };
+
+        for &part in &config.parts {
+            let mut outcome = solve(solution, part, input.text())?;
+            outcome.verdict = expected
+                .as_ref()
+                .map(|e| Verdict::of(&outcome.answer, e.part(part)));
+
+            match &outcome.verdict {
+                Some(verdict) => println!(
+                    "Day {:0>2} part {}: {} ({:?}) {}",
+                    outcome.day, outcome.part, outcome.answer, outcome.elapsed, verdict
+                ),
+                None => println!(
+                    "Day {:0>2} part {}: {} ({:?})",
+                    outcome.day, outcome.part, outcome.answer, outcome.elapsed
+                ),
+            }
+            outcomes.push(outcome);
+        }
+    }
+
+    if config.days.len() > 1 {
+        print_summary(&outcomes, &skipped);
+    }
+
+    let wrong = outcomes
+        .iter()
+        .filter(|o| matches!(o.verdict, Some(Verdict::Wrong(_))))
+        .count();
+    if wrong > 0 {
+        return Err(format!("{wrong} of {} answers are wrong", outcomes.len()).into());
+    }
+
+    Ok(())
+}
+
+fn solve(solution: &Day, part: u8, input: &str) -> Result<Outcome, ParseError> {
+    let solve = match part {
+        1 => solution.part_1,
+        2 => solution.part_2,
+        _ => unreachable!(),
+    };
+
+    let start = Instant::now();
+    let answer = solve(input)?;
+    let elapsed = start.elapsed();
+
+    Ok(Outcome {
+        day: solution.day,
+        part,
+        answer,
+        elapsed,
+        verdict: None,
+    })
+}
+
+fn print_summary(outcomes: &[Outcome], skipped: &[u8]) {
+    let width = outcomes
+        .iter()
+        .map(|o| o.answer.to_string().len())
+        .max()
+        .unwrap_or(0)
+        .max("Answer".len());
+
+    let verify = outcomes.iter().any(|o| o.verdict.is_some());
+    let status = |verdict: &Option<Verdict>| match verdict {
+        Some(verdict) if verify => format!(" {verdict} |"),
+        _ => String::new(),
+    };
+
+    println!();
+    println!(
+        "| Day | Part | {:<width$} | {:>12} |{}",
+        "Answer",
+        "Time",
+        if verify { " Status |" } else { "" }
+    );
+    println!(
+        "|-----|------|-{}-|-{}-|{}",
+        "-".repeat(width),
+        "-".repeat(12),
+        if verify { "--------|" } else { "" }
+    );
+    for o in outcomes {
+        println!(
+            "| {:>3} | {:>4} | {:<width$} | {:>12} |{}",
+            o.day,
+            o.part,
+            o.answer.to_string(),
+            format!("{:.2?}", o.elapsed),
+            status(&o.verdict)
+        );
+    }
+
+    let total: Duration = outcomes.iter().map(|o| o.elapsed).sum();
+    println!("Total time: {:.2?}", total);
+
+    if skipped.is_empty().not() {
+        println!(
+            "Skipped (no input): {}",
+            skipped.iter().map(|day| format!("{day:0>2}")).join(", ")
+        );
+    }
+
+    if verify {
+        let count = |f: fn(&Verdict) -> bool| {
+            outcomes
+                .iter()
+                .filter(|o| o.verdict.as_ref().is_some_and(f))
+                .count()
+        };
+        println!(
+            "Verified: {} correct, {} wrong, {} unknown",
+            count(|v| *v == Verdict::Correct),
+            count(|v| matches!(v, Verdict::Wrong(_))),
+            count(|v| *v == Verdict::Unknown)
+        );
+    }
+}
@@ -1,24 +1,26 @@
 use std::error::Error;
-use std::fs::File;
+use std::fs::{self, File};
 use std::io::Write;
-use std::ops::Not;
+use std::ops::{Not, RangeInclusive};
+use std::path::Path;
 use std::str::FromStr;
 use std::{env, process};
 
-use chrono::Datelike;
+use chrono::{Datelike, FixedOffset, Utc};
 
 /// Binary to scaffold code for a new Advent of Code day.
 ///
 /// # How to run
 ///
-/// This will scaffold all code for the day it is ran on:
+/// This will scaffold all code for the day it is ran on, refusing to guess outside of December 1-25:
 /// ```shell
 /// $ cargo run --bin scaffold
 /// ```
 ///
-/// This will scaffold all code for "Day 07":
+/// This will scaffold all code for "Day 07", or for "Day 07" up to and including "Day 09":
 /// ```shell
 /// $ cargo run --bin scaffold -- 7
+/// $ cargo run --bin scaffold -- 7-9
 /// ```
 ///
 /// Alternatively the binary can be built and called directly:
@@ -27,14 +29,22 @@ use chrono::Datelike;
 /// $ ./target/release/scaffold 7
 /// ```
 ///
-/// Optionally an environment variable `OVERWRITE` can be set,
-/// this will overwrite contents of existing files,
-/// normally existing files would throw an error.
+/// Besides the input, problem, (expected) answers, solution and benchmark files, the day gets registered
+/// as a module and in the solution registry of `solutions.rs`, and as a `[[bench]]` in `Cargo.toml`.
+/// Running it again for the same day is safe, nothing gets registered twice.
+///
+/// # Options
+///
+/// - `--template <lines|str|grid|param>` picks the shape of the solution, defaults to `lines`
+/// - `--year <YYYY>` is the event to link the puzzle and input of, defaults to 2015
+/// - `--dry-run` only prints the files that would be created or changed
+/// - `--overwrite` overwrites the contents of existing files, normally these are left untouched
 fn main() {
     let args: Vec<String> = env::args().collect();
 
     let config = Config::build(&args).unwrap_or_else(|err| {
         eprintln!("Problem parsing arguments: {err}");
+        eprintln!("{USAGE}");
         process::exit(1);
     });
 
@@ -44,48 +54,182 @@ fn main() {
     }
 }
 
+const USAGE: &str = "\
+Usage: scaffold [DAY | FROM-TO] [--template <lines|str|grid|param>] [--year <YYYY>] [--dry-run] [--overwrite]";
+
+const DEFAULT_YEAR: u16 = 2015;
+
+/// Advent of Code unlocks its puzzles at midnight EST.
+const PUZZLE_TIME_OFFSET: i32 = -5 * 3_600;
+
+#[derive(Debug)]
 struct Config {
-    day: u8,
+    days: RangeInclusive<u8>,
+    year: u16,
+    template: Template,
+    dry_run: bool,
     overwrite: bool,
 }
 
 impl Config {
-    fn build(args: &[String]) -> Result<Config, &'static str> {
-        let day = if args.len() >= 2 {
-            u8::from_str(&args[1])
-        } else {
-            Ok(chrono::prelude::Utc::now().day() as u8)
+    fn build(args: &[String]) -> Result<Config, String> {
+        let mut days = None;
+        let mut year = DEFAULT_YEAR;
+        let mut template = Template::Lines;
+        let mut dry_run = false;
+        let mut overwrite = false;
+
+        let mut args = args.iter().skip(1);
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--dry-run" => dry_run = true,
+                "--overwrite" => overwrite = true,
+                "--year" => {
+                    let value = args.next().ok_or("missing value for '--year'")?;
+                    year = u16::from_str(value)
+                        .ok()
+                        .filter(|&y| y >= DEFAULT_YEAR)
+                        .ok_or(format!("invalid year '{value}', expected 2015 or later"))?;
+                }
+                "--template" => {
+                    let value = args.next().ok_or("missing value for '--template'")?;
+                    template = Template::from_str(value)?;
+                }
+                a if a.starts_with("--") => return Err(format!("unknown argument '{a}'")),
+                a if days.is_none() => days = Some(Self::days(a)?),
+                a => return Err(format!("unexpected argument '{a}'")),
+            }
+        }
+
+        let days = match days {
+            Some(days) => days,
+            None => Self::today()?,
+        };
+
+        Ok(Config {
+            days,
+            year,
+            template,
+            dry_run,
+            overwrite,
+        })
+    }
+
+    fn days(days: &str) -> Result<RangeInclusive<u8>, String> {
+        let day = |d: &str| {
+            u8::from_str(d)
+                .ok()
+                .filter(|d| (1..=25).contains(d))
+                .ok_or(format!("invalid day '{d}', expected 1-25"))
         };
-        if day.is_err() {
-            return Err("invalid day");
+
+        match days.split_once('-') {
+            Some((from, to)) => {
+                let (from, to) = (day(from)?, day(to)?);
+                if from > to {
+                    return Err(format!("invalid day range '{days}'"));
+                }
+                Ok(from..=to)
+            }
+            None => day(days).map(|d| d..=d),
         }
-        let day = day.unwrap();
+    }
 
-        let overwrite = env::var("OVERWRITE").is_ok();
+    fn today() -> Result<RangeInclusive<u8>, String> {
+        let now = Utc::now().with_timezone(&FixedOffset::east_opt(PUZZLE_TIME_OFFSET).unwrap());
 
-        Ok(Config { day, overwrite })
+        if now.month() == 12 && now.day() <= 25 {
+            let day = now.day() as u8;
+            Ok(day..=day)
+        } else {
+            Err("no day given and today is not an Advent of Code day".to_string())
+        }
     }
 }
 
 fn run(config: Config) -> Result<(), Box<dyn Error>> {
-    let day = format!("day_{:0>2}", config.day);
+    for nr in config.days.clone() {
+        scaffold(&config, nr)?;
+    }
 
-    write_file(&config, &format!("./inputs/{}.txt", day), "")?;
-    write_file(&config, &format!("./problems/{}.txt", day), "")?;
+    Ok(())
+}
+
+fn scaffold(config: &Config, nr: u8) -> Result<(), Box<dyn Error>> {
+    let day = format!("day_{:0>2}", nr);
+    let fill = |template: &str| {
+        config
+            .template
+            .fill(template)
+            .replace("{day}", &day)
+            .replace("{dd}", &format!("{:0>2}", nr))
+            .replace("{nr}", &nr.to_string())
+    };
+
+    write_file(config, &format!("./inputs/{}.txt", day), "")?;
+    write_file(config, &format!("./problems/{}.txt", day), "")?;
+    write_file(config, &format!("./answers/{}.txt", day), "")?;
     write_file(
-        &config,
+        config,
         &format!("./src/solutions/{}.rs", day),
-        &TEMPLATE.replace("{}", &day),
+        &fill(TEMPLATE),
+    )?;
+    write_file(
+        config,
+        &format!("./benches/{}_bench.rs", day),
+        &fill(BENCH_TEMPLATE),
+    )?;
+
+    insert_line(
+        config,
+        "./src/solutions.rs",
+        &format!("pub mod {};", day),
+        Position::AfterLast("pub mod day_"),
+    )?;
+    insert_line(
+        config,
+        "./src/solutions.rs",
+        &fill("        Day::of::<{day}::Day{dd}>(),"),
+        Position::AfterLast("        Day::of::<day_"),
+    )?;
+    insert_line(
+        config,
+        "./Cargo.toml",
+        &fill("\n[[bench]]\nname = \"{day}_bench\"\nharness = false"),
+        Position::End,
     )?;
 
-    append_file("./src/solutions.rs", &format!("pub mod {};\n", day))?;
+    println!(
+        "Puzzle: https://adventofcode.com/{}/day/{}",
+        config.year, nr
+    );
+    println!(
+        "Input:  https://adventofcode.com/{}/day/{}/input",
+        config.year, nr
+    );
 
     Ok(())
 }
 
 fn write_file(config: &Config, path: &str, content: &str) -> Result<(), Box<dyn Error>> {
+    let exists = Path::new(path).exists();
+
+    if config.overwrite.not() && exists {
+        println!("Skipping existing file {path}");
+        return Ok(());
+    }
+    if config.dry_run {
+        let action = if exists { "overwrite" } else { "create" };
+        println!("Would {action} {path}");
+        return Ok(());
+    }
+
+    if let Some(dir) = Path::new(path).parent() {
+        fs::create_dir_all(dir)?;
+    }
+
     File::options()
-        .create_new(config.overwrite.not())
+        .create(true)
         .write(true)
         .truncate(true)
         .open(path)?
@@ -94,56 +238,275 @@ fn write_file(config: &Config, path: &str, content: &str) -> Result<(), Box<dyn
     Ok(())
 }
 
-fn append_file(path: &str, content: &str) -> Result<(), Box<dyn Error>> {
-    File::options()
-        .write(true)
-        .append(true)
-        .open(path)?
-        .write_all(content.as_ref())?;
+enum Position<'a> {
+    AfterLast(&'a str),
+    End,
+}
+
+/// Inserts `line` into the file at `path`, unless the file already contains it.
+fn insert_line(
+    config: &Config,
+    path: &str,
+    line: &str,
+    position: Position,
+) -> Result<(), Box<dyn Error>> {
+    let content = fs::read_to_string(path)?;
+
+    if content.contains(line) {
+        println!("Skipping {path}, already contains {}", summary(line));
+        return Ok(());
+    }
+
+    let content = match position {
+        Position::AfterLast(prefix) => {
+            let mut lines: Vec<&str> = content.lines().collect();
+            let idx = lines
+                .iter()
+                .rposition(|l| l.starts_with(prefix))
+                .ok_or(format!("could not find '{}' in {path}", prefix.trim()))?;
+            lines.insert(idx + 1, line);
+
+            lines.join("\n") + "\n"
+        }
+        Position::End => format!("{}\n{line}\n", content.trim_end()),
+    };
+
+    if config.dry_run {
+        println!("Would add {} to {path}", summary(line));
+        return Ok(());
+    }
+
+    fs::write(path, content)?;
 
     Ok(())
 }
 
-const TEMPLATE: &str = "\
-// use std::str::FromStr;
+/// Collapses a (multi-line) snippet onto one line for printing.
+fn summary(line: &str) -> String {
+    line.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Shape of the generated solution, each filling in the placeholders of [`TEMPLATE`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum Template {
+    /// One item per line, solved as `&[&str]`.
+    Lines,
+    /// A single line or blob of text, solved as `&str`.
+    Str,
+    /// A grid of characters, parsed from its lines.
+    Grid,
+    /// One item per line, with an extra puzzle parameter such as a number of steps.
+    Param,
+}
+
+impl FromStr for Template {
+    type Err = String;
+
+    fn from_str(template: &str) -> Result<Self, Self::Err> {
+        match template {
+            "lines" => Ok(Template::Lines),
+            "str" => Ok(Template::Str),
+            "grid" => Ok(Template::Grid),
+            "param" => Ok(Template::Param),
+            _ => Err(format!(
+                "unknown template '{template}', expected one of lines, str, grid or param"
+            )),
+        }
+    }
+}
+
+impl Template {
+    fn fill(&self, template: &str) -> String {
+        let lines = [
+            ("{raw}", "lines: &[&str]"),
+            ("{raw_name}", "lines"),
+            ("{parsed}", "[&str]"),
+            (
+                "{parse_sig}",
+                "<'a>(lines: &[&'a str]) -> Result<Vec<&'a str>, ParseError>",
+            ),
+            ("{parse_body}", "parse_lines(lines, Ok)"),
+            ("{input}", "Vec<&'a str>"),
+            ("{view}", "&input.lines().collect_vec()"),
+            ("{sample}", "vec![\"\", \"\", \"\"]"),
+            ("{sample_ref}", "&sample"),
+            ("{load}", "lines()"),
+            ("{input_ref}", "&input"),
+        ];
+        let replacements = match self {
+            Template::Lines | Template::Param => lines.to_vec(),
+            Template::Str => vec![
+                ("{raw}", "input: &str"),
+                ("{raw_name}", "input"),
+                ("{parsed}", "str"),
+                ("{parse_sig}", "(input: &str) -> Result<&str, ParseError>"),
+                ("{parse_body}", "Ok(input)"),
+                ("{input}", "&'a str"),
+                ("{view}", "input"),
+                ("{sample}", "\"\""),
+                ("{sample_ref}", "sample"),
+                ("{load}", "text()"),
+                ("{input_ref}", "input"),
+            ],
+            Template::Grid => vec![
+                ("{raw}", "grid: &[&str]"),
+                ("{raw_name}", "grid"),
+                ("{parsed}", "[Vec<char>]"),
+                (
+                    "{parse_sig}",
+                    "(grid: &[&str]) -> Result<Vec<Vec<char>>, ParseError>",
+                ),
+                (
+                    "{parse_body}",
+                    "Ok(grid.iter().map(|row| row.chars().collect()).collect())",
+                ),
+                ("{input}", "Vec<Vec<char>>"),
+                ("{view}", "&input.lines().collect_vec()"),
+                ("{sample}", "vec![\"...\", \"...\", \"...\"]"),
+                ("{sample_ref}", "&sample"),
+                ("{load}", "lines()"),
+                ("{input_ref}", "&input"),
+            ],
+        };
+        let (consts, param, arg, const_arg, sample_arg, use_param) = match self {
+            Template::Param => (
+                "\nconst STEPS: u32 = 100;\n",
+                ", steps: u32",
+                ", steps",
+                ", STEPS",
+                ", 10",
+                " * steps",
+            ),
+            _ => ("", "", "", "", "", ""),
+        };
+        let borrow = match self {
+            Template::Str => "",
+            _ => "&",
+        };
+        let imports = match self {
+            Template::Str => "",
+            _ => "use itertools::Itertools;\n\n",
+        };
+        let error_imports = match self {
+            Template::Lines | Template::Param => "{parse_lines, ParseError}",
+            _ => "ParseError",
+        };
+
+        replacements
+            .into_iter()
+            .chain([
+                ("{imports}", imports),
+                ("{error_imports}", error_imports),
+                ("{consts}", consts),
+                ("{param}", param),
+                ("{arg}", arg),
+                ("{const_arg}", const_arg),
+                ("{sample_arg}", sample_arg),
+                ("{use_param}", use_param),
+                ("{borrow}", borrow),
+            ])
+            .fold(template.to_string(), |t, (from, to)| t.replace(from, to))
+    }
+}
+
+const TEMPLATE: &str = r#"// use std::str::FromStr;
 
 // use regex::Regex;
 
-pub fn solve_1(X: &[&str]) -> u32 {
-    42
+{imports}use crate::error::{error_imports};
+use crate::solutions::Solution;
+{consts}
+pub fn solve_1({raw}{param}) -> Result<u32, ParseError> {
+    Ok(part_1({borrow}parse({raw_name})?{arg}))
+}
+
+pub fn solve_2({raw}{param}) -> Result<u32, ParseError> {
+    Ok(part_2({borrow}parse({raw_name})?{arg}))
+}
+
+fn part_1(input: &{parsed}{param}) -> u32 {
+    input.len() as u32{use_param}
+}
+
+fn part_2(input: &{parsed}{param}) -> u32 {
+    input.len() as u32{use_param}
+}
+
+fn parse{parse_sig} {
+    {parse_body}
+}
+
+pub struct Day{dd};
+
+impl Solution for Day{dd} {
+    const DAY: u8 = {nr};
+
+    type Input<'a> = {input};
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse({view})
+    }
+
+    fn part_1(input: &Self::Input<'_>) -> Self::Answer1 {
+        part_1(input{const_arg})
+    }
+
+    fn part_2(input: &Self::Input<'_>) -> Self::Answer2 {
+        part_2(input{const_arg})
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use itertools::Itertools;
-
     use super::*;
+    use crate::util::Input;
 
     #[test]
-    fn {}_part_01_sample() {
-        let sample = vec![
-            \"\",
-            \"\",
-            \"\",
-            \"\",
-            \"\",
-            \"\",
-            \"\",
-            \"\",
-            \"\",
-            \"\",
-        ];
+    fn {day}_part_01_sample() {
+        let sample = {sample};
+
+        assert_eq!(Ok(42), solve_1({sample_ref}{sample_arg}));
+    }
+
+    #[test]
+    fn {day}_part_01_solution() {
+        let input = Input::load({nr}).unwrap().{load};
 
-        assert_eq!(42, solve_1(&sample));
+        assert_eq!(Ok(0), solve_1({input_ref}{const_arg}));
     }
 
     #[test]
-    fn {}_part_01_solution() {
-        let input = include_str!(\"../../inputs/{}.txt\")
-            .lines()
-            .collect_vec();
+    fn {day}_part_02_sample() {
+        let sample = {sample};
 
-        assert_eq!(0, solve_1(&input));
+        assert_eq!(Ok(42), solve_2({sample_ref}{sample_arg}));
     }
+
+    #[test]
+    fn {day}_part_02_solution() {
+        let input = Input::load({nr}).unwrap().{load};
+
+        assert_eq!(Ok(0), solve_2({input_ref}{const_arg}));
+    }
+}
+"#;
+
+const BENCH_TEMPLATE: &str = r#"use criterion::{criterion_group, criterion_main, Criterion};
+
+use advent_of_code_2015::solutions::*;
+
+mod common;
+
+/// Run this benchmark using
+/// ```shell
+/// $ cargo bench --bench {day}_bench
+/// ```
+fn day{dd}(c: &mut Criterion) {
+    common::bench::<{day}::Day{dd}>(c);
 }
-";
+
+criterion_group!(benches, day{dd});
+criterion_main!(benches);
+"#;
@@ -0,0 +1,280 @@
+use std::error::Error;
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+use std::str::FromStr;
+use std::{env, process};
+
+use advent_of_code_2015::solutions::day_22::{
+    self, Actor, Book, Day22, Game, GameState, Rules, Turn,
+};
+use advent_of_code_2015::solutions::Solution;
+use advent_of_code_2015::util::Input;
+
+/// Binary to play the wizard fight of "Day 22" turn by turn.
+///
+/// # How to run
+///
+/// This will fight the boss of `inputs/day_22.txt`, with the stats and spells of the puzzle:
+/// ```shell
+/// $ cargo run --release --bin wizard
+/// ```
+///
+/// On every turn of the player, cast a spell by typing its number or name.
+/// Typing `hint` shows the spell to cast for the cheapest win from the current turn on, `quit` ends the fight.
+///
+/// # Options
+///
+/// - `--input <path>` is the boss to fight, defaults to `inputs/day_22.txt`
+/// - `--config <path>` holds the stats of the player and their spells, as text or JSON (see `day_22::Config`)
+/// - `--hard` plays the fight of part 2, the player losing 1 hit point at the start of each of their turns
+/// - `--drain <N>` makes the player lose `N` (more) hit points at the start of each of their turns
+/// - `--boss-damage <PERCENT>` scales the damage of the boss
+/// - `--mana-cost <PERCENT>` scales the cost of every spell
+/// - `--effect-turns <N>` makes every effect last `N` turns longer, or shorter when negative
+///
+/// Difficulty options add up, `--hard --drain 2` drains 3 hit points a turn
+/// and `--mana-cost 200 --mana-cost 150` triples the cost of every spell.
+fn main() {
+    let args: Vec<String> = env::args().collect();
+
+    let config = Config::build(&args).unwrap_or_else(|err| {
+        eprintln!("Problem parsing arguments: {err}");
+        eprintln!("{USAGE}");
+        process::exit(1);
+    });
+
+    if let Err(e) = run(config) {
+        eprintln!("Application error: {e}");
+        process::exit(1);
+    }
+}
+
+const USAGE: &str = "\
+Usage: wizard [--input <path>] [--config <path>] [--hard] [--drain <N>] [--boss-damage <PERCENT>]
+              [--mana-cost <PERCENT>] [--effect-turns <N>]";
+
+#[derive(Debug)]
+struct Config {
+    input: Option<String>,
+    config: Option<String>,
+    rules: Rules,
+}
+
+impl Config {
+    fn build(args: &[String]) -> Result<Config, String> {
+        let mut input = None;
+        let mut config = None;
+        let mut rules = Rules::default();
+
+        let mut args = args.iter().skip(1);
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--input" => input = Some(Self::path(args.next(), arg)?),
+                "--config" => config = Some(Self::path(args.next(), arg)?),
+                "--hard" => rules = rules.and(Rules::hard()),
+                "--drain" => rules = rules.with_drain(Self::number(args.next(), arg)?),
+                "--boss-damage" => rules = rules.with_boss_damage(Self::number(args.next(), arg)?),
+                "--mana-cost" => rules = rules.with_mana_cost(Self::number(args.next(), arg)?),
+                "--effect-turns" => {
+                    rules = rules.with_effect_turns(Self::number(args.next(), arg)?)
+                }
+                _ => return Err(format!("unknown argument '{arg}'")),
+            }
+        }
+
+        Ok(Config {
+            input,
+            config,
+            rules,
+        })
+    }
+
+    fn path(value: Option<&String>, flag: &str) -> Result<String, String> {
+        value.cloned().ok_or(format!("missing value for '{flag}'"))
+    }
+
+    fn number<T: FromStr>(value: Option<&String>, flag: &str) -> Result<T, String> {
+        let value = value.ok_or(format!("missing value for '{flag}'"))?;
+
+        T::from_str(value).map_err(|_| format!("invalid value '{value}' for '{flag}'"))
+    }
+}
+
+/// What the player typed on their turn.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum Command {
+    Cast(usize),
+    Hint,
+    Quit,
+}
+
+impl Command {
+    fn new(command: &str, book: &Book) -> Result<Self, String> {
+        let command = command.trim();
+
+        match command.to_lowercase().as_str() {
+            "" => Err("type the number or name of a spell, 'hint' or 'quit'".to_string()),
+            "h" | "hint" => Ok(Command::Hint),
+            "q" | "quit" | "exit" => Ok(Command::Quit),
+            name => usize::from_str(name)
+                .ok()
+                .and_then(|nr| nr.checked_sub(1))
+                .filter(|&idx| idx < book.spells().len())
+                .or_else(|| {
+                    book.spells()
+                        .iter()
+                        .position(|s| s.name.to_lowercase() == name)
+                })
+                .map(Command::Cast)
+                .ok_or(format!("unknown spell '{command}'")),
+        }
+    }
+}
+
+fn run(config: Config) -> Result<(), Box<dyn Error>> {
+    let input = match &config.input {
+        Some(path) => Input::from_path(Day22::DAY, Path::new(path))?,
+        None => Input::load(Day22::DAY)?.clone(),
+    };
+    let boss = Day22::parse(input.text())?.boss();
+    let setup = match &config.config {
+        Some(path) => day_22::Config::new(&fs::read_to_string(path)?)?,
+        None => day_22::Config::default(),
+    };
+    let book = &setup.book;
+
+    let mut game = Game::new(boss, &setup.player);
+    let mut spent_mana = 0;
+
+    println!(
+        "You have {} hit points and {} mana, the boss has {} hit points and deals {} damage",
+        game.player_hp(),
+        game.mana(),
+        game.boss_hp(),
+        game.boss_damage()
+    );
+
+    loop {
+        match game.state() {
+            GameState::Won => {
+                println!("You won, spending {spent_mana} mana");
+                return Ok(());
+            }
+            GameState::Lost => {
+                println!("You lost");
+                return Ok(());
+            }
+            GameState::OnGoing => {}
+        }
+
+        let options = game.play(book, &config.rules);
+        let (spell, next) = match (game.actor(), options.as_slice()) {
+            (_, []) => {
+                println!("You cannot go on, you lost");
+                return Ok(());
+            }
+            (Actor::Player, [(Some(_), _), ..]) => {
+                match choose(&game, book, &options, &config.rules)? {
+                    Some(option) => option,
+                    None => return Ok(()),
+                }
+            }
+            // The boss attacking, or effects finishing off the boss before the player gets to cast a spell
+            (_, [option, ..]) => *option,
+        };
+
+        spent_mana += spell.map_or(0, |idx| config.rules.cost(&book.spells()[idx]));
+        println!("{}", Turn::new(book, &game, spell, &next));
+        game = next;
+    }
+}
+
+/// Asks the player which spell to cast until they pick one that can be cast, `None` if they quit.
+fn choose(
+    game: &Game,
+    book: &Book,
+    options: &[(Option<usize>, Game)],
+    rules: &Rules,
+) -> Result<Option<(Option<usize>, Game)>, io::Error> {
+    let option = |idx| options.iter().find(|(spell, _)| *spell == Some(idx));
+
+    for (idx, spell) in book.spells().iter().enumerate() {
+        let available = match option(idx) {
+            Some(_) => "",
+            None => " (unavailable)",
+        };
+        println!(
+            "  {}. {:<14} {:>4} mana{available}",
+            idx + 1,
+            spell.name,
+            rules.cost(spell)
+        );
+    }
+
+    loop {
+        print!("> ");
+        io::stdout().flush()?;
+
+        let mut line = String::new();
+        if io::stdin().read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+
+        match Command::new(&line, book) {
+            Ok(Command::Cast(idx)) => match option(idx) {
+                Some(&option) => return Ok(Some(option)),
+                None => println!("{} cannot be cast right now", book.spells()[idx].name),
+            },
+            Ok(Command::Hint) => match day_22::finish(*game, book, rules) {
+                Some(fight) => println!(
+                    "Cast {}, the cheapest win spends {} more mana",
+                    fight.turns[0].spell.as_ref().map_or("nothing", |s| &s.name),
+                    fight.spent_mana
+                ),
+                None => println!("There is no way left to win"),
+            },
+            Ok(Command::Quit) => return Ok(None),
+            Err(e) => println!("{e}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn difficulty_options_add_up() {
+        let args = [
+            "wizard",
+            "--hard",
+            "--drain",
+            "2",
+            "--mana-cost",
+            "200",
+            "--mana-cost",
+            "150",
+        ];
+        let config = Config::build(&args.map(String::from)).unwrap();
+
+        assert_eq!(
+            Rules::default().with_drain(3).with_mana_cost(300),
+            config.rules
+        );
+        assert!(Config::build(&["wizard", "--drain", "x"].map(String::from)).is_err());
+    }
+
+    #[test]
+    fn commands_pick_spells_by_number_or_name() {
+        let book = Book::default();
+
+        assert_eq!(Ok(Command::Cast(0)), Command::new("1\n", &book));
+        assert_eq!(Ok(Command::Cast(3)), Command::new(" poison ", &book));
+        assert_eq!(Ok(Command::Cast(0)), Command::new("Magic Missile", &book));
+        assert_eq!(Ok(Command::Hint), Command::new("hint", &book));
+        assert_eq!(Ok(Command::Quit), Command::new("q", &book));
+        assert!(Command::new("6", &book).is_err());
+        assert!(Command::new("Fireball", &book).is_err());
+    }
+}
@@ -0,0 +1,167 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::path::PathBuf;
+use std::str::FromStr;
+
+/// Error raised when a puzzle input does not match the expected format.
+///
+/// Both `line` and `column` are 1-based, `text` holds the full offending line.
+#[derive(Debug, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub struct ParseError {
+    pub day: u8,
+    pub line: usize,
+    pub column: usize,
+    pub text: String,
+    pub reason: String,
+}
+
+impl ParseError {
+    /// Creates an error on `text`, pointing at `at` which should be a sub-slice of `text`.
+    ///
+    /// The line number defaults to 1, use [`ParseError::on_line`] to report a different line.
+    pub fn new(day: u8, text: &str, at: &str, reason: impl Into<String>) -> Self {
+        let start = text.as_ptr() as usize;
+        let offset = (at.as_ptr() as usize)
+            .checked_sub(start)
+            .filter(|&o| o <= text.len())
+            .unwrap_or(0);
+
+        Self {
+            day,
+            line: 1,
+            column: text[..offset].chars().count() + 1,
+            text: text.to_string(),
+            reason: reason.into(),
+        }
+    }
+
+    pub fn on_line(self, line: usize) -> Self {
+        Self { line, ..self }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "day {:0>2}, line {}, column {}: {}",
+            self.day, self.line, self.column, self.reason
+        )?;
+        writeln!(f, "    {}", self.text)?;
+        write!(f, "    {:>width$}", "^", width = self.column)
+    }
+}
+
+impl Error for ParseError {}
+
+/// Error raised when a puzzle input cannot be loaded from disk.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum InputError {
+    Missing {
+        day: u8,
+        path: PathBuf,
+    },
+    Unreadable {
+        day: u8,
+        path: PathBuf,
+        reason: String,
+    },
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputError::Missing { day, path } => write!(
+                f,
+                "input for day {:0>2} not found at '{}', download it from https://adventofcode.com/2015/day/{}/input",
+                day,
+                path.display(),
+                day
+            ),
+            InputError::Unreadable { day, path, reason } => write!(
+                f,
+                "input for day {:0>2} at '{}' could not be read: {}",
+                day,
+                path.display(),
+                reason
+            ),
+        }
+    }
+}
+
+impl Error for InputError {}
+
+/// Parses `at`, a sub-slice of `text`, into a number, reporting its position on failure.
+pub fn parse_number<T>(day: u8, text: &str, at: &str) -> Result<T, ParseError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    T::from_str(at)
+        .map_err(|e| ParseError::new(day, text, at, format!("invalid number '{at}': {e}")))
+}
+
+/// Parses every line using `parse`, attaching the (1-based) line number to any error.
+pub fn parse_lines<'a, T>(
+    lines: &[&'a str],
+    parse: impl Fn(&'a str) -> Result<T, ParseError>,
+) -> Result<Vec<T>, ParseError> {
+    lines
+        .iter()
+        .enumerate()
+        .map(|(idx, line)| parse(line).map_err(|e| e.on_line(idx + 1)))
+        .collect()
+}
+
+/// Reports invalid JSON at the line and column `serde_json` found it.
+pub fn json_error(day: u8, json: &str, error: &serde_json::Error) -> ParseError {
+    let text = json
+        .lines()
+        .nth(error.line().max(1) - 1)
+        .unwrap_or_default();
+    // The column counts bytes, so point at the start of the character holding that byte
+    let offset = text
+        .char_indices()
+        .map(|(idx, _)| idx)
+        .take_while(|&idx| idx < error.column().max(1))
+        .last()
+        .unwrap_or(0);
+
+    ParseError::new(day, text, &text[offset..], error.to_string()).on_line(error.line())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn error_points_at_offending_text() {
+        let text = "turn on 0,x through 2,2";
+        let error = parse_number::<u32>(6, text, &text[10..11]).unwrap_err();
+
+        assert_eq!(1, error.line);
+        assert_eq!(11, error.column);
+        assert_eq!(
+            "day 06, line 1, column 11: invalid number 'x': invalid digit found in string\n    \
+             turn on 0,x through 2,2\n    \
+             \x20         ^",
+            error.to_string()
+        );
+    }
+
+    #[test]
+    fn json_error_points_at_character() {
+        let json = "[\"é\", é]";
+        let error = serde_json::from_str::<serde_json::Value>(json).unwrap_err();
+        let error = json_error(12, json, &error);
+
+        assert_eq!((1, 7), (error.line, error.column));
+    }
+
+    #[test]
+    fn error_reports_line_number() {
+        let error = parse_lines(&["1", "2", "three"], |l| parse_number::<u8>(1, l, l));
+
+        assert_eq!(3, error.unwrap_err().line);
+    }
+}
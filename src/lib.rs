@@ -1,2 +1,4 @@
+pub mod answer;
+pub mod error;
 pub mod solutions;
-mod util;
+pub mod util;
@@ -1,3 +1,8 @@
+use std::collections::BTreeMap;
+
+use crate::answer::{Answer, IntoAnswer};
+use crate::error::ParseError;
+
 pub mod day_01;
 pub mod day_02;
 pub mod day_03;
@@ -23,3 +28,85 @@ pub mod day_22;
 pub mod day_23;
 pub mod day_24;
 pub mod day_25;
+
+/// A single Advent of Code day, exposing both parts behind a common interface.
+///
+/// The input is parsed once, reporting malformed input as a [`ParseError`],
+/// and can then be shared between both parts, each part returning its own typed answer.
+pub trait Solution {
+    const DAY: u8;
+
+    type Input<'a>;
+    type Answer1: IntoAnswer;
+    type Answer2: IntoAnswer;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError>;
+
+    fn part_1(input: &Self::Input<'_>) -> Self::Answer1;
+
+    fn part_2(input: &Self::Input<'_>) -> Self::Answer2;
+}
+
+/// Type-erased entry in the [`registry`], allowing any day to be invoked generically.
+#[derive(Debug, Copy, Clone)]
+pub struct Day {
+    pub day: u8,
+    pub part_1: fn(&str) -> Result<Answer, ParseError>,
+    pub part_2: fn(&str) -> Result<Answer, ParseError>,
+}
+
+impl Day {
+    pub fn of<S: Solution>() -> Self {
+        Self {
+            day: S::DAY,
+            part_1: |input| S::part_1(&S::parse(input)?).into_answer(),
+            part_2: |input| S::part_2(&S::parse(input)?).into_answer(),
+        }
+    }
+}
+
+/// All solved days, keyed by their day number.
+pub fn registry() -> BTreeMap<u8, Day> {
+    [
+        Day::of::<day_01::Day01>(),
+        Day::of::<day_02::Day02>(),
+        Day::of::<day_03::Day03>(),
+        Day::of::<day_04::Day04>(),
+        Day::of::<day_05::Day05>(),
+        Day::of::<day_06::Day06>(),
+        Day::of::<day_07::Day07>(),
+        Day::of::<day_08::Day08>(),
+        Day::of::<day_09::Day09>(),
+        Day::of::<day_10::Day10>(),
+        Day::of::<day_11::Day11>(),
+        Day::of::<day_12::Day12>(),
+        Day::of::<day_13::Day13>(),
+        Day::of::<day_14::Day14>(),
+        Day::of::<day_15::Day15>(),
+        Day::of::<day_16::Day16>(),
+        Day::of::<day_17::Day17>(),
+        Day::of::<day_18::Day18>(),
+        Day::of::<day_19::Day19>(),
+        Day::of::<day_20::Day20>(),
+        Day::of::<day_21::Day21>(),
+        Day::of::<day_22::Day22>(),
+        Day::of::<day_23::Day23>(),
+        Day::of::<day_24::Day24>(),
+        Day::of::<day_25::Day25>(),
+    ]
+    .into_iter()
+    .map(|d| (d.day, d))
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registry_covers_all_days() {
+        let registry = registry();
+
+        assert!((1..=25).all(|day| registry[&day].day == day));
+    }
+}
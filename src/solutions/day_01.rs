@@ -1,68 +1,140 @@
-pub fn solve_1(instructions: &str) -> i16 {
-    instructions
-        .chars()
-        .map(|c| match c {
-            '(' => 1,
-            ')' => -1,
-            _ => unreachable!(),
-        })
-        .sum()
+use crate::error::ParseError;
+use crate::solutions::Solution;
+
+pub fn solve_1(instructions: &str) -> Result<i16, ParseError> {
+    Ok(final_floor(&parse(instructions)?))
+}
+
+pub fn solve_2(instructions: &str) -> Result<u16, ParseError> {
+    basement_position(&parse(instructions)?)
 }
 
-pub fn solve_2(instructions: &str) -> u16 {
+fn final_floor(instructions: &Instructions) -> i16 {
+    instructions.steps.iter().sum()
+}
+
+fn basement_position(instructions: &Instructions) -> Result<u16, ParseError> {
     let mut position = 0;
     let mut floor = 0;
 
-    for c in instructions.chars() {
+    for step in &instructions.steps {
         position += 1;
-        floor += match c {
-            '(' => 1,
-            ')' => -1,
-            _ => unreachable!(),
-        };
+        floor += step;
 
         if floor < 0 {
-            return position;
+            return Ok(position);
         }
     }
 
-    unreachable!()
+    let text = instructions.text;
+    Err(ParseError::new(
+        Day01::DAY,
+        text,
+        &text[text.len()..],
+        "the instructions never reach the basement",
+    ))
+}
+
+/// The parsed steps up or down, along with their text to point at in errors.
+pub struct Instructions<'a> {
+    text: &'a str,
+    steps: Vec<i16>,
+}
+
+fn parse(instructions: &str) -> Result<Instructions<'_>, ParseError> {
+    let steps = instructions
+        .char_indices()
+        .map(|(idx, c)| match c {
+            '(' => Ok(1),
+            ')' => Ok(-1),
+            _ => Err(ParseError::new(
+                Day01::DAY,
+                instructions,
+                &instructions[idx..],
+                format!("unexpected character '{c}'"),
+            )),
+        })
+        .collect::<Result<_, _>>()?;
+
+    Ok(Instructions {
+        text: instructions,
+        steps,
+    })
+}
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    const DAY: u8 = 1;
+
+    type Input<'a> = Instructions<'a>;
+    type Answer1 = i16;
+    type Answer2 = Result<u16, ParseError>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input.trim())
+    }
+
+    fn part_1(input: &Self::Input<'_>) -> Self::Answer1 {
+        final_floor(input)
+    }
+
+    fn part_2(input: &Self::Input<'_>) -> Self::Answer2 {
+        basement_position(input)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::util::Input;
 
     #[test]
     fn day_01_part_01_sample() {
-        assert_eq!(0, solve_1("(())"));
-        assert_eq!(0, solve_1("()()"));
-        assert_eq!(3, solve_1("((("));
-        assert_eq!(3, solve_1("(()(()("));
-        assert_eq!(3, solve_1("))((((("));
-        assert_eq!(-1, solve_1("())"));
-        assert_eq!(-1, solve_1("))("));
-        assert_eq!(-3, solve_1(")))"));
-        assert_eq!(-3, solve_1(")())())"));
+        assert_eq!(Ok(0), solve_1("(())"));
+        assert_eq!(Ok(0), solve_1("()()"));
+        assert_eq!(Ok(3), solve_1("((("));
+        assert_eq!(Ok(3), solve_1("(()(()("));
+        assert_eq!(Ok(3), solve_1("))((((("));
+        assert_eq!(Ok(-1), solve_1("())"));
+        assert_eq!(Ok(-1), solve_1("))("));
+        assert_eq!(Ok(-3), solve_1(")))"));
+        assert_eq!(Ok(-3), solve_1(")())())"));
     }
 
     #[test]
     fn day_01_part_01_solution() {
-        let input = include_str!("../../inputs/day_01.txt").trim();
+        let input = Input::load(1).unwrap().text();
 
-        assert_eq!(74, solve_1(input));
+        assert_eq!(Ok(74), solve_1(input));
     }
 
     #[test]
     fn day_01_part_02_sample() {
-        assert_eq!(1, solve_2(")"));
-        assert_eq!(5, solve_2("()())"));
+        assert_eq!(Ok(1), solve_2(")"));
+        assert_eq!(Ok(5), solve_2("()())"));
+    }
+
+    #[test]
+    fn day_01_never_in_basement() {
+        let error = solve_2("(()").unwrap_err();
+
+        assert_eq!((1, 4), (error.line, error.column));
+        assert!(solve_2("").is_err());
+        assert_eq!(Ok(0), solve_1(""));
     }
 
     #[test]
     fn day_01_part_02_solution() {
-        let input = include_str!("../../inputs/day_01.txt").trim();
+        let input = Input::load(1).unwrap().text();
+
+        assert_eq!(Ok(1_795), solve_2(input));
+    }
+
+    #[test]
+    fn day_01_parse_error() {
+        let error = solve_1("(()x").unwrap_err();
 
-        assert_eq!(1_795, solve_2(input));
+        assert_eq!((1, 4), (error.line, error.column));
     }
 }
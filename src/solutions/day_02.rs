@@ -1,40 +1,49 @@
-use std::str::FromStr;
-
 use itertools::Itertools;
 
-pub fn solve_1(presents: &[&str]) -> u32 {
-    presents
-        .iter()
-        .map(|p| Present::new(p))
-        .map(|p| p.surface() + p.slack())
-        .sum()
+use crate::error::{parse_lines, parse_number, ParseError};
+use crate::solutions::Solution;
+
+pub fn solve_1(presents: &[&str]) -> Result<u32, ParseError> {
+    Ok(wrapping_paper(&parse_lines(presents, Present::new)?))
+}
+
+pub fn solve_2(presents: &[&str]) -> Result<u32, ParseError> {
+    Ok(ribbon(&parse_lines(presents, Present::new)?))
+}
+
+fn wrapping_paper(presents: &[Present]) -> u32 {
+    presents.iter().map(|p| p.surface() + p.slack()).sum()
 }
 
-pub fn solve_2(presents: &[&str]) -> u32 {
+fn ribbon(presents: &[Present]) -> u32 {
     presents
         .iter()
-        .map(|p| Present::new(p))
         .map(|p| p.shortest_distance() + p.volume())
         .sum()
 }
 
 #[derive(Debug)]
-struct Present {
+pub struct Present {
     l: u32,
     w: u32,
     h: u32,
 }
 
 impl Present {
-    fn new(present: &str) -> Self {
+    fn new(present: &str) -> Result<Self, ParseError> {
         let split = present
             .split('x')
-            .map(|s| u32::from_str(s).unwrap())
-            .collect_vec();
+            .map(|s| parse_number(Day02::DAY, present, s))
+            .collect::<Result<Vec<u32>, _>>()?;
 
         match split[..] {
-            [l, w, h] => Self { l, w, h },
-            _ => unreachable!(),
+            [l, w, h] => Ok(Self { l, w, h }),
+            _ => Err(ParseError::new(
+                Day02::DAY,
+                present,
+                present,
+                "expected dimensions 'LxWxH'",
+            )),
         }
     }
 
@@ -62,41 +71,65 @@ impl Present {
     }
 }
 
+pub struct Day02;
+
+impl Solution for Day02 {
+    const DAY: u8 = 2;
+
+    type Input<'a> = Vec<Present>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_lines(&input.lines().collect_vec(), Present::new)
+    }
+
+    fn part_1(input: &Self::Input<'_>) -> Self::Answer1 {
+        wrapping_paper(input)
+    }
+
+    fn part_2(input: &Self::Input<'_>) -> Self::Answer2 {
+        ribbon(input)
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use itertools::Itertools;
-
     use super::*;
+    use crate::util::Input;
 
     #[test]
     fn day_02_part_01_sample() {
         let sample = vec!["2x3x4", "1x1x10"];
 
-        assert_eq!(101, solve_1(&sample));
+        assert_eq!(Ok(101), solve_1(&sample));
     }
 
     #[test]
     fn day_02_part_01_solution() {
-        let input = include_str!("../../inputs/day_02.txt")
-            .lines()
-            .collect_vec();
+        let input = Input::load(2).unwrap().lines();
 
-        assert_eq!(1_606_483, solve_1(&input));
+        assert_eq!(Ok(1_606_483), solve_1(&input));
     }
 
     #[test]
     fn day_02_part_02_sample() {
         let sample = vec!["2x3x4", "1x1x10"];
 
-        assert_eq!(48, solve_2(&sample));
+        assert_eq!(Ok(48), solve_2(&sample));
     }
 
     #[test]
     fn day_02_part_02_solution() {
-        let input = include_str!("../../inputs/day_02.txt")
-            .lines()
-            .collect_vec();
+        let input = Input::load(2).unwrap().lines();
+
+        assert_eq!(Ok(3_842_356), solve_2(&input));
+    }
+
+    #[test]
+    fn day_02_parse_error() {
+        let error = solve_1(&["2x3x4", "1x1x1O"]).unwrap_err();
 
-        assert_eq!(3_842_356, solve_2(&input));
+        assert_eq!((2, 5), (error.line, error.column));
     }
 }
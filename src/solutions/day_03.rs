@@ -1,44 +1,40 @@
 use itertools::Itertools;
 use rustc_hash::FxHashSet;
 
-pub fn solve_1(directions: &str) -> usize {
-    houses(directions).len()
+use crate::error::ParseError;
+use crate::solutions::Solution;
+
+pub fn solve_1(directions: &str) -> Result<usize, ParseError> {
+    Ok(santa(&parse(directions)?))
+}
+
+pub fn solve_2(directions: &str) -> Result<usize, ParseError> {
+    Ok(santa_and_robot(&parse(directions)?))
 }
 
-pub fn solve_2(directions: &str) -> usize {
-    let santa: String = directions
-        .chars()
-        .enumerate()
-        .filter(|(idx, _)| idx % 2 == 0)
-        .map(|(_, d)| d)
-        .collect();
-    let robot_santa: String = directions
-        .chars()
-        .enumerate()
-        .filter(|(idx, _)| idx % 2 != 0)
-        .map(|(_, d)| d)
-        .collect();
-
-    houses(&santa)
-        .union(&houses(&robot_santa))
+fn santa(moves: &[Coordinate]) -> usize {
+    houses(moves.iter()).len()
+}
+
+fn santa_and_robot(moves: &[Coordinate]) -> usize {
+    let santa = moves.iter().step_by(2);
+    let robot_santa = moves.iter().skip(1).step_by(2);
+
+    houses(santa)
+        .union(&houses(robot_santa))
         .collect_vec()
         .len()
 }
 
-fn houses(directions: &str) -> FxHashSet<Coordinate> {
+fn houses<'a>(moves: impl Iterator<Item = &'a Coordinate>) -> FxHashSet<Coordinate> {
     let mut visited: FxHashSet<Coordinate> = FxHashSet::default();
     let mut current = Coordinate { x: 0, y: 0 };
 
     visited.insert(current);
 
-    for d in directions.chars() {
-        match d {
-            '^' => current.y += 1,
-            '>' => current.x += 1,
-            'v' => current.y -= 1,
-            '<' => current.x -= 1,
-            _ => unreachable!(),
-        }
+    for m in moves {
+        current.x += m.x;
+        current.y += m.y;
 
         visited.insert(current);
     }
@@ -46,41 +42,89 @@ fn houses(directions: &str) -> FxHashSet<Coordinate> {
     visited
 }
 
+fn parse(directions: &str) -> Result<Vec<Coordinate>, ParseError> {
+    directions
+        .char_indices()
+        .map(|(idx, d)| match d {
+            '^' => Ok(Coordinate { x: 0, y: 1 }),
+            '>' => Ok(Coordinate { x: 1, y: 0 }),
+            'v' => Ok(Coordinate { x: 0, y: -1 }),
+            '<' => Ok(Coordinate { x: -1, y: 0 }),
+            _ => Err(ParseError::new(
+                Day03::DAY,
+                directions,
+                &directions[idx..],
+                format!("unexpected direction '{d}'"),
+            )),
+        })
+        .collect()
+}
+
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
-struct Coordinate {
+pub struct Coordinate {
     x: i32,
     y: i32,
 }
 
+pub struct Day03;
+
+impl Solution for Day03 {
+    const DAY: u8 = 3;
+
+    type Input<'a> = Vec<Coordinate>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input.trim())
+    }
+
+    fn part_1(input: &Self::Input<'_>) -> Self::Answer1 {
+        santa(input)
+    }
+
+    fn part_2(input: &Self::Input<'_>) -> Self::Answer2 {
+        santa_and_robot(input)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::util::Input;
 
     #[test]
     fn day_03_part_01_sample() {
-        assert_eq!(2, solve_1(">"));
-        assert_eq!(4, solve_1("^>v<"));
-        assert_eq!(2, solve_1("^v^v^v^v^v"));
+        assert_eq!(Ok(2), solve_1(">"));
+        assert_eq!(Ok(4), solve_1("^>v<"));
+        assert_eq!(Ok(2), solve_1("^v^v^v^v^v"));
     }
 
     #[test]
     fn day_03_part_01_solution() {
-        let input = include_str!("../../inputs/day_03.txt").trim();
+        let input = Input::load(3).unwrap().text();
 
-        assert_eq!(2_592, solve_1(input));
+        assert_eq!(Ok(2_592), solve_1(input));
     }
 
     #[test]
     fn day_03_part_02_sample() {
-        assert_eq!(3, solve_2("^v"));
-        assert_eq!(3, solve_2("^>v<"));
-        assert_eq!(11, solve_2("^v^v^v^v^v"));
+        assert_eq!(Ok(3), solve_2("^v"));
+        assert_eq!(Ok(3), solve_2("^>v<"));
+        assert_eq!(Ok(11), solve_2("^v^v^v^v^v"));
     }
 
     #[test]
     fn day_03_part_02_solution() {
-        let input = include_str!("../../inputs/day_03.txt").trim();
+        let input = Input::load(3).unwrap().text();
+
+        assert_eq!(Ok(2_360), solve_2(input));
+    }
+
+    #[test]
+    fn day_03_parse_error() {
+        let error = solve_1("^>x<").unwrap_err();
 
-        assert_eq!(2_360, solve_2(input));
+        assert_eq!((1, 3), (error.line, error.column));
     }
 }
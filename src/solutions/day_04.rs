@@ -1,5 +1,8 @@
 use md5::{Digest, Md5};
 
+use crate::error::ParseError;
+use crate::solutions::Solution;
+
 pub fn solve_1(key: &str) -> u32 {
     solve(key, "00000")
 }
@@ -24,9 +27,32 @@ pub fn solve(key: &str, zeroes: &str) -> u32 {
     }
 }
 
+pub struct Day04;
+
+impl Solution for Day04 {
+    const DAY: u8 = 4;
+
+    type Input<'a> = &'a str;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        Ok(input.trim())
+    }
+
+    fn part_1(input: &Self::Input<'_>) -> Self::Answer1 {
+        solve_1(input)
+    }
+
+    fn part_2(input: &Self::Input<'_>) -> Self::Answer2 {
+        solve_2(input)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::util::Input;
 
     #[ignore = "slow brute force test"]
     #[test]
@@ -38,7 +64,7 @@ mod tests {
     #[ignore = "slow brute force test"]
     #[test]
     fn day_04_part_01_solution() {
-        let input = include_str!("../../inputs/day_04.txt").trim();
+        let input = Input::load(4).unwrap().text();
 
         assert_eq!(282_749, solve_1(input));
     }
@@ -52,7 +78,7 @@ mod tests {
     #[ignore = "slow brute force test"]
     #[test]
     fn day_04_part_02_solution() {
-        let input = include_str!("../../inputs/day_04.txt").trim();
+        let input = Input::load(4).unwrap().text();
 
         assert_eq!(9_962_624, solve_2(input));
     }
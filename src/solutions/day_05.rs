@@ -3,6 +3,9 @@ use std::ops::Not;
 use lazy_static::lazy_static;
 use regex::Regex;
 
+use crate::error::ParseError;
+use crate::solutions::Solution;
+
 pub fn solve_1(strings: &[&str]) -> usize {
     strings
         .iter()
@@ -53,11 +56,32 @@ fn has_double_between(ascii: &[u8]) -> bool {
     false
 }
 
+pub struct Day05;
+
+impl Solution for Day05 {
+    const DAY: u8 = 5;
+
+    type Input<'a> = Vec<&'a str>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        Ok(input.lines().collect())
+    }
+
+    fn part_1(input: &Self::Input<'_>) -> Self::Answer1 {
+        solve_1(input)
+    }
+
+    fn part_2(input: &Self::Input<'_>) -> Self::Answer2 {
+        solve_2(input)
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use itertools::Itertools;
-
     use super::*;
+    use crate::util::Input;
 
     #[test]
     fn day_05_part_01_sample() {
@@ -74,9 +98,7 @@ mod tests {
 
     #[test]
     fn day_05_part_01_solution() {
-        let input = include_str!("../../inputs/day_05.txt")
-            .lines()
-            .collect_vec();
+        let input = Input::load(5).unwrap().lines();
 
         assert_eq!(236, solve_1(&input));
     }
@@ -95,9 +117,7 @@ mod tests {
 
     #[test]
     fn day_05_part_02_solution() {
-        let input = include_str!("../../inputs/day_05.txt")
-            .lines()
-            .collect_vec();
+        let input = Input::load(5).unwrap().lines();
 
         assert_eq!(51, solve_2(&input));
     }
@@ -1,55 +1,410 @@
-use std::str::FromStr;
+use std::ops::{Not, Range};
 
 use itertools::Itertools;
 use lazy_static::lazy_static;
+use rayon::prelude::*;
 use regex::Regex;
 
-pub fn solve_1(instructions: &[&str]) -> usize {
-    let mut grid = [[false; 1000]; 1000];
-    let instructions = instructions
+use crate::error::{parse_lines, parse_number, ParseError};
+use crate::solutions::Solution;
+
+pub fn solve_1(instructions: &[&str]) -> Result<u128, ParseError> {
+    Ok(lights_on(&parse_lines(instructions, Instruction::new)?))
+}
+
+pub fn solve_2(instructions: &[&str]) -> Result<u128, ParseError> {
+    Ok(brightness(&parse_lines(instructions, Instruction::new)?))
+}
+
+fn lights_on(instructions: &[Instruction]) -> u128 {
+    run(instructions, &Switch)
+}
+
+fn brightness(instructions: &[Instruction]) -> u128 {
+    run(instructions, &Brightness)
+}
+
+pub fn render_1(instructions: &[&str]) -> Result<Vec<u8>, ParseError> {
+    Ok(pbm(&parse_lines(instructions, Instruction::new)?))
+}
+
+pub fn render_2(instructions: &[&str]) -> Result<Vec<u8>, ParseError> {
+    Ok(pgm(
+        &parse_lines(instructions, Instruction::new)?,
+        &Brightness,
+    ))
+}
+
+/// Describes what a single light looks like, how each [`Action`] changes it,
+/// and how much it counts towards the total once all instructions ran.
+///
+/// Changes are [`LightModel::Effect`]s rather than functions, so the effect of many actions in a row
+/// can be combined into a single one without knowing the light they apply to.
+pub trait LightModel: Sync {
+    type Light: Copy + Default + Send;
+    type Effect: Copy + Send + Sync;
+
+    fn effect(&self, action: Action) -> Self::Effect;
+
+    /// The effect of `first` followed by `second`.
+    fn then(&self, first: Self::Effect, second: Self::Effect) -> Self::Effect;
+
+    fn apply(&self, effect: Self::Effect, light: Self::Light) -> Self::Light;
+
+    fn value(&self, light: Self::Light) -> u64;
+}
+
+/// Lights that are either on or off, counting the lights that are on.
+pub struct Switch;
+
+impl LightModel for Switch {
+    type Light = bool;
+    /// Sets the light when `Some`, then flips it when `true`.
+    type Effect = (Option<bool>, bool);
+
+    fn effect(&self, action: Action) -> Self::Effect {
+        match action {
+            Action::TurnOn => (Some(true), false),
+            Action::TurnOff => (Some(false), false),
+            Action::Toggle => (None, true),
+        }
+    }
+
+    fn then(&self, first: Self::Effect, second: Self::Effect) -> Self::Effect {
+        match second {
+            (Some(_), _) => second,
+            (None, flip) => (first.0, first.1 ^ flip),
+        }
+    }
+
+    fn apply(&self, (set, flip): Self::Effect, light: bool) -> bool {
+        set.unwrap_or(light) ^ flip
+    }
+
+    fn value(&self, light: bool) -> u64 {
+        light as u64
+    }
+}
+
+/// Lights with a brightness of zero or more, summing the brightness of all lights.
+pub struct Brightness;
+
+impl LightModel for Brightness {
+    type Light = u32;
+    /// Adds the first number to the brightness, keeping it at least the second number.
+    type Effect = (i64, i64);
+
+    fn effect(&self, action: Action) -> Self::Effect {
+        match action {
+            Action::TurnOn => (1, 0),
+            Action::TurnOff => (-1, 0),
+            Action::Toggle => (2, 0),
+        }
+    }
+
+    fn then(&self, (add_1, min_1): Self::Effect, (add_2, min_2): Self::Effect) -> Self::Effect {
+        (add_1 + add_2, (min_1 + add_2).max(min_2))
+    }
+
+    fn apply(&self, (add, min): Self::Effect, light: u32) -> u32 {
+        (light as i64 + add).max(min) as u32
+    }
+
+    fn value(&self, light: u32) -> u64 {
+        light as u64
+    }
+}
+
+/// Runs all instructions on a grid of any size, summing the value of every light at the end.
+///
+/// The sum easily outgrows a `u64` on huge grids, so it is a `u128`.
+pub fn run<M: LightModel>(instructions: &[Instruction], model: &M) -> u128 {
+    Grid::switch(instructions, model).total(model)
+}
+
+/// Renders the lights after running all instructions as a binary PBM image, lit lights being white.
+pub fn pbm(instructions: &[Instruction]) -> Vec<u8> {
+    let grid = Grid::switch(instructions, &Switch);
+    let (width, height) = grid.size();
+
+    let mut image = format!("P4\n{width} {height}\n").into_bytes();
+    for y in 0..height {
+        let row = (0..width).map(|x| grid.light(x, y)).collect_vec();
+        image.extend(row.chunks(8).map(|byte| {
+            byte.iter()
+                .enumerate()
+                .filter(|(_, &light)| !light)
+                .fold(0u8, |bits, (bit, _)| bits | 0x80 >> bit)
+        }));
+    }
+
+    image
+}
+
+/// Renders the lights after running all instructions as a binary PGM greyscale image,
+/// the brightest light being white.
+pub fn pgm<M: LightModel>(instructions: &[Instruction], model: &M) -> Vec<u8> {
+    let grid = Grid::switch(instructions, model);
+    let (width, height) = grid.size();
+    let max = grid
+        .rows
         .iter()
-        .map(|i| Instruction::new(i))
-        .collect_vec();
-
-    #[allow(clippy::needless_range_loop)]
-    for i in instructions {
-        for x in i.x_min..=i.x_max {
-            for y in i.y_min..=i.y_max {
-                match i.action {
-                    Action::TurnOn => grid[y][x] = true,
-                    Action::TurnOff => grid[y][x] = false,
-                    Action::Toggle => grid[y][x] = !grid[y][x],
-                }
+        .flatten()
+        .map(|&light| model.value(light))
+        .max()
+        .unwrap_or(0)
+        .clamp(1, u16::MAX as u64);
+
+    let mut image = format!("P5\n{width} {height}\n{max}\n").into_bytes();
+    for y in 0..height {
+        for x in 0..width {
+            let value = model.value(grid.light(x, y)).min(max) as u16;
+            match max {
+                0..=255 => image.push(value as u8),
+                _ => image.extend(value.to_be_bytes()),
             }
         }
     }
 
-    grid.iter()
-        .map(|lights| lights.iter().filter(|&&light| light).count())
-        .sum()
+    image
 }
 
-pub fn solve_2(instructions: &[&str]) -> i32 {
-    let mut grid = vec![vec![0i32; 1000]; 1000];
-    let instructions = instructions
-        .iter()
-        .map(|i| Instruction::new(i))
-        .collect_vec();
-
-    #[allow(clippy::needless_range_loop)]
-    for i in instructions {
-        for x in i.x_min..=i.x_max {
-            for y in i.y_min..=i.y_max {
-                match i.action {
-                    Action::TurnOn => grid[y][x] += 1,
-                    Action::TurnOff => grid[y][x] = i32::max(grid[y][x] - 1, 0),
-                    Action::Toggle => grid[y][x] += 2,
+/// Final state of all lights, compressed into blocks of lights that are always switched together.
+///
+/// Block `rows[r][c]` spans the lights from `xs[c]` up to `xs[c + 1]` and from `ys[r]` up to `ys[r + 1]`.
+struct Grid<L> {
+    xs: Vec<usize>,
+    ys: Vec<usize>,
+    rows: Vec<Vec<L>>,
+}
+
+impl<L: Copy + Default + Send> Grid<L> {
+    /// Runs all instructions without visiting every single light, bands of rows of blocks in parallel.
+    ///
+    /// Going down a band, a row only differs from the row above where an instruction starts or ends between them,
+    /// so only those blocks are switched again. Switching a row of blocks sweeps them from left to right,
+    /// keeping the combined effect of the instructions covering the current block in an [`Effects`] tree.
+    fn switch<M: LightModel<Light = L>>(instructions: &[Instruction], model: &M) -> Self {
+        let instructions = instructions
+            .iter()
+            .filter(|i| i.x_min <= i.x_max && i.y_min <= i.y_max)
+            .collect_vec();
+        let xs = boundaries(instructions.iter().map(|i| (i.x_min, i.x_max)));
+        let ys = boundaries(instructions.iter().map(|i| (i.y_min, i.y_max)));
+        let (width, height) = (xs.len().saturating_sub(1), ys.len().saturating_sub(1));
+
+        let block = |bounds: &[usize], min: usize, max: usize| {
+            bounds.binary_search(&min).unwrap()..bounds.binary_search(&(max + 1)).unwrap()
+        };
+        let blocks = instructions
+            .iter()
+            .map(|i| Blocks {
+                columns: block(&xs, i.x_min, i.x_max),
+                rows: block(&ys, i.y_min, i.y_max),
+                effect: model.effect(i.action),
+            })
+            .collect_vec();
+        // Instructions starting or ending at each column, from left to right
+        let changes = blocks
+            .iter()
+            .enumerate()
+            .flat_map(|(idx, b)| [(b.columns.start, idx), (b.columns.end, idx)])
+            .sorted_unstable()
+            .collect_vec();
+        // Instructions starting or ending at each row
+        let mut edges = vec![vec![]; height + 1];
+        for (idx, b) in blocks.iter().enumerate() {
+            edges[b.rows.start].push(idx);
+            edges[b.rows.end].push(idx);
+        }
+
+        // Switches the `dirty` blocks of a row again, leaving the other blocks as they are
+        let sweep = |row: usize, dirty: Vec<Range<usize>>, lights: &mut [L]| {
+            let covering = (0..blocks.len())
+                .filter(|&idx| blocks[idx].rows.contains(&row))
+                .collect_vec();
+            let mut places = vec![None; blocks.len()];
+            for (place, &idx) in covering.iter().enumerate() {
+                places[idx] = Some(place);
+            }
+
+            for columns in dirty {
+                let effect = |idx: usize, column| {
+                    let b = &blocks[idx];
+                    b.columns.contains(&column).then_some(b.effect)
+                };
+                let mut effects = Effects::new(
+                    model,
+                    covering
+                        .iter()
+                        .map(|&idx| effect(idx, columns.start))
+                        .collect(),
+                );
+                let from = changes.partition_point(|&(column, _)| column <= columns.start);
+                let mut changes = changes[from..]
+                    .iter()
+                    .filter_map(|&(column, idx)| Some((column, idx, places[idx]?)))
+                    .peekable();
+
+                for column in columns {
+                    while let Some((_, idx, place)) = changes.next_if(|&(at, _, _)| at == column) {
+                        effects.set(place, effect(idx, column));
+                    }
+                    lights[column] = effects
+                        .total()
+                        .map_or(L::default(), |effect| model.apply(effect, L::default()));
                 }
             }
+        };
+
+        let rows = (0..height)
+            .into_par_iter()
+            .chunks(BAND)
+            .flat_map_iter(|band| {
+                let mut lights = vec![L::default(); width];
+                band.into_iter()
+                    .enumerate()
+                    .map(|(idx, row)| {
+                        // The lights of a band start off, so its first row switches the blocks of every instruction on it
+                        let dirty = match idx {
+                            0 => merged(
+                                blocks
+                                    .iter()
+                                    .filter(|b| b.rows.contains(&row))
+                                    .map(|b| b.columns.clone()),
+                            ),
+                            _ => merged(edges[row].iter().map(|&idx| blocks[idx].columns.clone())),
+                        };
+                        sweep(row, dirty, &mut lights);
+                        lights.clone()
+                    })
+                    .collect_vec()
+            })
+            .collect();
+
+        Self { xs, ys, rows }
+    }
+
+    fn total<M: LightModel<Light = L>>(&self, model: &M) -> u128 {
+        self.rows
+            .iter()
+            .zip(self.ys.windows(2))
+            .map(|(lights, row)| {
+                let height = (row[1] - row[0]) as u128;
+                lights
+                    .iter()
+                    .zip(self.xs.windows(2))
+                    .map(|(&light, column)| {
+                        model.value(light) as u128 * (column[1] - column[0]) as u128 * height
+                    })
+                    .sum::<u128>()
+            })
+            .sum()
+    }
+
+    /// Width and height of the smallest grid starting at `0,0` holding every switched light.
+    fn size(&self) -> (usize, usize) {
+        (
+            self.xs.last().copied().unwrap_or(0),
+            self.ys.last().copied().unwrap_or(0),
+        )
+    }
+
+    fn light(&self, x: usize, y: usize) -> L {
+        let block = |bounds: &[usize], at: usize| {
+            bounds
+                .partition_point(|&b| b <= at)
+                .checked_sub(1)
+                .filter(|&idx| idx + 1 < bounds.len())
+        };
+
+        match (block(&self.xs, x), block(&self.ys, y)) {
+            (Some(column), Some(row)) => self.rows[row][column],
+            _ => L::default(),
+        }
+    }
+}
+
+/// Rows of blocks in a band of [`Grid::switch`], the first row of a band being switched from scratch.
+const BAND: usize = 64;
+
+/// The blocks an instruction switches, and its effect on them.
+struct Blocks<E> {
+    columns: Range<usize>,
+    rows: Range<usize>,
+    effect: E,
+}
+
+/// Sorted and merged overlapping ranges.
+fn merged(ranges: impl Iterator<Item = Range<usize>>) -> Vec<Range<usize>> {
+    ranges
+        .sorted_unstable_by_key(|range| range.start)
+        .coalesce(|a, b| match b.start <= a.end {
+            true => Ok(a.start..a.end.max(b.end)),
+            false => Err((a, b)),
+        })
+        .filter(|range| range.is_empty().not())
+        .collect()
+}
+
+/// Segment tree combining the effects of instructions in the order they were given,
+/// instructions without an effect being left out.
+struct Effects<'a, M: LightModel> {
+    model: &'a M,
+    leaves: usize,
+    tree: Vec<Option<M::Effect>>,
+}
+
+impl<'a, M: LightModel> Effects<'a, M> {
+    /// Combines the given effects, one for every instruction.
+    fn new(model: &'a M, effects: Vec<Option<M::Effect>>) -> Self {
+        let leaves = effects.len().next_power_of_two();
+        let mut tree = vec![None; leaves];
+        tree.extend(effects);
+        tree.resize(2 * leaves, None);
+
+        let mut effects = Self {
+            model,
+            leaves,
+            tree,
+        };
+        for node in (1..leaves).rev() {
+            effects.tree[node] = effects.combine(node);
+        }
+        effects
+    }
+
+    fn combine(&self, node: usize) -> Option<M::Effect> {
+        match (self.tree[2 * node], self.tree[2 * node + 1]) {
+            (Some(first), Some(second)) => Some(self.model.then(first, second)),
+            (first, None) => first,
+            (None, second) => second,
         }
     }
 
-    grid.iter().map(|lights| lights.iter().sum::<i32>()).sum()
+    fn set(&mut self, instruction: usize, effect: Option<M::Effect>) {
+        let mut node = self.leaves + instruction;
+        self.tree[node] = effect;
+
+        while node > 1 {
+            node /= 2;
+            self.tree[node] = self.combine(node);
+        }
+    }
+
+    /// The effect of all instructions together, `None` if none of them has an effect.
+    fn total(&self) -> Option<M::Effect> {
+        self.tree[1]
+    }
+}
+
+/// Sorted coordinates at which a range of lights starts or ends, the end being exclusive.
+fn boundaries(ranges: impl Iterator<Item = (usize, usize)>) -> Vec<usize> {
+    ranges
+        .flat_map(|(min, max)| [min, max + 1])
+        .sorted()
+        .dedup()
+        .collect()
 }
 
 lazy_static! {
@@ -60,7 +415,7 @@ lazy_static! {
 }
 
 #[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
-struct Instruction {
+pub struct Instruction {
     action: Action,
     x_min: usize,
     y_min: usize,
@@ -69,42 +424,77 @@ struct Instruction {
 }
 
 impl Instruction {
-    fn new(instruction: &str) -> Instruction {
-        let caps = RE.captures(instruction).unwrap();
+    fn new(instruction: &str) -> Result<Instruction, ParseError> {
+        let caps = RE.captures(instruction).ok_or_else(|| {
+            ParseError::new(
+                Day06::DAY,
+                instruction,
+                instruction,
+                "expected '<turn on|turn off|toggle> X,Y through X,Y'",
+            )
+        })?;
 
-        let action = match caps.name("action").unwrap().as_str() {
+        let action = match &caps["action"] {
             "turn on" => Action::TurnOn,
             "turn off" => Action::TurnOff,
-            "toggle" => Action::Toggle,
-            _ => unreachable!(),
+            _ => Action::Toggle,
+        };
+        let coordinate =
+            |name| parse_number(Day06::DAY, instruction, caps.name(name).unwrap().as_str());
+        // The grid is split right after the last light of an instruction, so there has to be room for that
+        let last = |name| match coordinate(name)? {
+            usize::MAX => Err(ParseError::new(
+                Day06::DAY,
+                instruction,
+                caps.name(name).unwrap().as_str(),
+                format!("expected a coordinate below {}", usize::MAX),
+            )),
+            value => Ok(value),
         };
-        let x_min = usize::from_str(caps.name("x_min").unwrap().as_str()).unwrap();
-        let y_min = usize::from_str(caps.name("y_min").unwrap().as_str()).unwrap();
-        let x_max = usize::from_str(caps.name("x_max").unwrap().as_str()).unwrap();
-        let y_max = usize::from_str(caps.name("y_max").unwrap().as_str()).unwrap();
 
-        Instruction {
+        Ok(Instruction {
             action,
-            x_min,
-            y_min,
-            x_max,
-            y_max,
-        }
+            x_min: coordinate("x_min")?,
+            y_min: coordinate("y_min")?,
+            x_max: last("x_max")?,
+            y_max: last("y_max")?,
+        })
     }
 }
 
 #[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
-enum Action {
+pub enum Action {
     TurnOn,
     TurnOff,
     Toggle,
 }
 
+pub struct Day06;
+
+impl Solution for Day06 {
+    const DAY: u8 = 6;
+
+    type Input<'a> = Vec<Instruction>;
+    type Answer1 = u128;
+    type Answer2 = u128;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_lines(&input.lines().collect_vec(), Instruction::new)
+    }
+
+    fn part_1(input: &Self::Input<'_>) -> Self::Answer1 {
+        lights_on(input)
+    }
+
+    fn part_2(input: &Self::Input<'_>) -> Self::Answer2 {
+        brightness(input)
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use itertools::Itertools;
-
     use super::*;
+    use crate::util::Input;
 
     #[test]
     fn day_06_part_01_sample() {
@@ -114,31 +504,161 @@ mod tests {
             "turn off 499,499 through 500,500",
         ];
 
-        assert_eq!(998_996, solve_1(&sample));
+        assert_eq!(Ok(998_996), solve_1(&sample));
     }
 
     #[test]
     fn day_06_part_01_solution() {
-        let input = include_str!("../../inputs/day_06.txt")
-            .lines()
-            .collect_vec();
+        let input = Input::load(6).unwrap().lines();
 
-        assert_eq!(377_891, solve_1(&input));
+        assert_eq!(Ok(377_891), solve_1(&input));
     }
 
     #[test]
     fn day_06_part_02_sample() {
         let sample = vec!["turn on 0,0 through 0,0", "toggle 0,0 through 999,999"];
 
-        assert_eq!(2_000_001, solve_2(&sample));
+        assert_eq!(Ok(2_000_001), solve_2(&sample));
     }
 
     #[test]
     fn day_06_part_02_solution() {
-        let input = include_str!("../../inputs/day_06.txt")
-            .lines()
+        let input = Input::load(6).unwrap().lines();
+
+        assert_eq!(Ok(14_110_788), solve_2(&input));
+    }
+
+    #[test]
+    fn day_06_huge_grid() {
+        let sample = vec![
+            "turn on 0,0 through 999999999,999999999",
+            "toggle 0,0 through 999999999,0",
+            "turn off 499999999,499999999 through 500000000,500000000",
+            "toggle 1,1 through 0,0",
+        ];
+
+        assert_eq!(Ok(999_999_998_999_999_996), solve_1(&sample));
+        assert_eq!(Ok(1_000_000_001_999_999_996), solve_2(&sample));
+
+        let sample = ["toggle 0,0 through 999999999,999999999"; 10];
+
+        assert_eq!(Ok(0), solve_1(&sample));
+        assert_eq!(Ok(20_000_000_000_000_000_000), solve_2(&sample));
+    }
+
+    #[test]
+    fn day_06_many_instructions() {
+        let actions = ["turn on", "turn off", "toggle"];
+        let sample = (0..150)
+            .map(|i| {
+                let (x, y) = (i * 7 % 101, i * 13 % 97);
+                format!(
+                    "{} {x},{y} through {},{}",
+                    actions[i % 3],
+                    x + i % 50,
+                    y + i % 70
+                )
+            })
             .collect_vec();
+        let sample = sample.iter().map(String::as_str).collect_vec();
+
+        let mut lights = vec![vec![(false, 0); 200]; 200];
+        for i in parse_lines(&sample, Instruction::new).unwrap() {
+            for light in lights[i.x_min..=i.x_max]
+                .iter_mut()
+                .flat_map(|column| column[i.y_min..=i.y_max].iter_mut())
+            {
+                *light = (
+                    Switch.apply(Switch.effect(i.action), light.0),
+                    Brightness.apply(Brightness.effect(i.action), light.1),
+                );
+            }
+        }
+        let lights = lights.into_iter().flatten().collect_vec();
+
+        assert_eq!(
+            Ok(lights.iter().filter(|light| light.0).count() as u128),
+            solve_1(&sample)
+        );
+        assert_eq!(
+            Ok(lights.iter().map(|light| light.1 as u128).sum()),
+            solve_2(&sample)
+        );
+    }
+
+    #[test]
+    fn day_06_custom_model() {
+        struct Cycle;
+
+        impl LightModel for Cycle {
+            type Light = u8;
+            type Effect = [u8; 3];
+
+            fn effect(&self, action: Action) -> [u8; 3] {
+                match action {
+                    Action::TurnOn => [1; 3],
+                    Action::TurnOff => [0; 3],
+                    Action::Toggle => [1, 2, 0],
+                }
+            }
+
+            fn then(&self, first: [u8; 3], second: [u8; 3]) -> [u8; 3] {
+                first.map(|light| second[light as usize])
+            }
+
+            fn apply(&self, effect: [u8; 3], light: u8) -> u8 {
+                effect[light as usize]
+            }
+
+            fn value(&self, light: u8) -> u64 {
+                light as u64
+            }
+        }
+
+        let sample = ["toggle 0,0 through 9,9", "toggle 0,0 through 4,9"];
+        let instructions = parse_lines(&sample, Instruction::new).unwrap();
+
+        assert_eq!(50 * 2 + 50, run(&instructions, &Cycle));
+    }
+
+    #[test]
+    fn day_06_render() {
+        let sample = vec![
+            "turn on 0,0 through 9,1",
+            "toggle 0,0 through 0,1",
+            "turn on 0,1 through 0,1",
+        ];
+
+        assert_eq!(
+            b"P4\n10 2\n\x80\x00\x00\x00".to_vec(),
+            render_1(&sample).unwrap()
+        );
+        assert_eq!(
+            [
+                b"P5\n10 2\n4\n".as_slice(),
+                &[3, 1, 1, 1, 1, 1, 1, 1, 1, 1],
+                &[4, 1, 1, 1, 1, 1, 1, 1, 1, 1]
+            ]
+            .concat(),
+            render_2(&sample).unwrap()
+        );
+    }
+
+    #[test]
+    fn day_06_parse_error() {
+        let error = solve_1(&["turn on 0,0 through 9,9", "turn of 0,0 through 9,9"]).unwrap_err();
+
+        assert_eq!((2, 1), (error.line, error.column));
 
-        assert_eq!(14_110_788, solve_2(&input));
+        let error = solve_1(&["turn on 0,0 through 18446744073709551615,0"]).unwrap_err();
+        assert_eq!((1, 21), (error.line, error.column));
+        assert_eq!(
+            "expected a coordinate below 18446744073709551615",
+            error.reason
+        );
+        assert_eq!(
+            Ok(2),
+            solve_1(&["turn on 18446744073709551613,0 through 18446744073709551614,0"])
+        );
     }
 }
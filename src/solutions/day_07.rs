@@ -1,64 +1,562 @@
+use std::collections::VecDeque;
+use std::error::Error;
+use std::fmt::{Debug, Display, Formatter};
+use std::hash::Hash;
+use std::marker::PhantomData;
+use std::ops::{BitAnd, BitOr, Not};
 use std::str::FromStr;
 
+use itertools::Itertools;
 use lazy_static::lazy_static;
 use regex::Regex;
-use rustc_hash::FxHashMap;
+use rustc_hash::{FxHashMap, FxHashSet};
 
-pub fn solve_1(circuit: &[&str]) -> u16 {
+use crate::error::{parse_lines, parse_number, ParseError};
+use crate::solutions::Solution;
+
+pub fn solve_1(circuit: &[&str]) -> Result<u16, ParseError> {
     solve(circuit, false)
 }
 
-pub fn solve_2(circuit: &[&str]) -> u16 {
+pub fn solve_2(circuit: &[&str]) -> Result<u16, ParseError> {
     solve(circuit, true)
 }
 
-pub fn solve(circuit: &[&str], override_b: bool) -> u16 {
-    let circuit = circuit
-        .iter()
-        .map(|c| Component::new(c))
-        .map(|c| (c.destination(), c))
-        .collect();
-    let mut cache: FxHashMap<&str, u16> = FxHashMap::default();
-
+pub fn solve(circuit: &[&str], override_b: bool) -> Result<u16, ParseError> {
+    let parsed = parse(circuit)?;
     if override_b {
-        let a = signal(&circuit, &mut cache, "a");
-        cache.clear();
-        cache.insert("b", a);
+        driven(&parsed, circuit, "b")?;
     }
 
-    signal(&circuit, &mut cache, "a")
+    Ok(simulate(&parsed, override_b))
+}
+
+/// Computes the signal on every wire of the circuit, after overriding the signals in `overrides`.
+///
+/// Overrides may also give a signal to wires no component drives, making them inputs of the circuit.
+/// The width of the signals follows from `W`, the puzzle itself using 16-bit signals.
+pub fn signals<'a, W: Word>(
+    circuit: &[&'a str],
+    overrides: &FxHashMap<&str, W>,
+) -> Result<FxHashMap<&'a str, W>, ParseError> {
+    let components = parse_lines(circuit, Component::new)?;
+    let inputs = overrides.keys().copied().collect_vec();
+
+    Circuit::with_inputs(components, &inputs)
+        .and_then(|c| c.signals(overrides))
+        .map_err(|e| e.into_parse_error(circuit))
+}
+
+/// Draws the circuit as a Graphviz DOT graph, optionally labelling every wire with its signal.
+pub fn dot(circuit: &[&str], annotate: bool) -> Result<String, ParseError> {
+    let circuit: Circuit = build(circuit)?;
+    let signals = annotate.then(|| circuit.signals(&FxHashMap::default()).unwrap());
+
+    Ok(circuit.dot(signals.as_ref()))
 }
 
-fn signal<'a>(
-    ci: &FxHashMap<&'a str, Component<'a>>,
-    ch: &mut FxHashMap<&'a str, u16>,
-    wire: &'a str,
-) -> u16 {
-    if let Some(value) = ch.get(wire) {
-        return *value;
+/// Simplifies the circuit driving `target` into a netlist, keeping the wires in `inputs` undriven.
+pub fn simplify(lines: &[&str], target: &str, inputs: &[&str]) -> Result<String, ParseError> {
+    let components = parse_lines(lines, Component::new)?;
+    let circuit: Circuit =
+        Circuit::with_inputs(components, inputs).map_err(|e| e.into_parse_error(lines))?;
+    driven(&circuit, lines, target)?;
+
+    Ok(circuit.simplify(target, inputs).unwrap())
+}
+
+/// Runs the circuit, which parsing checked drives both `a` and (when overriding it) `b`.
+fn simulate(circuit: &Circuit, override_b: bool) -> u16 {
+    let a = circuit.signals(&FxHashMap::default()).unwrap()["a"];
+
+    match override_b {
+        true => circuit.signals(&FxHashMap::from_iter([("b", a)])).unwrap()["a"],
+        false => a,
     }
-    let result = if let Ok(value) = u16::from_str(wire) {
-        value
-    } else {
-        match ci[wire] {
-            Component::Wire { source, .. } => signal(ci, ch, source),
-            Component::And {
-                source_a, source_b, ..
-            } => signal(ci, ch, source_a) & signal(ci, ch, source_b),
-            Component::Or {
-                source_a, source_b, ..
-            } => signal(ci, ch, source_a) | signal(ci, ch, source_b),
-            Component::Not { source, .. } => !signal(ci, ch, source),
-            Component::LShift { source, value, .. } => signal(ci, ch, source) << value,
-            Component::RShift { source, value, .. } => signal(ci, ch, source) >> value,
-        }
+}
+
+fn build<'a, W: Word>(lines: &[&'a str]) -> Result<Circuit<'a, W>, ParseError> {
+    let components = parse_lines(lines, Component::new)?;
+
+    Circuit::new(components).map_err(|e| e.into_parse_error(lines))
+}
+
+fn parse<'a>(lines: &[&'a str]) -> Result<Circuit<'a>, ParseError> {
+    let circuit = build(lines)?;
+    driven(&circuit, lines, "a")?;
+
+    Ok(circuit)
+}
+
+/// Reports an error at the end of the input when nothing drives `wire`.
+fn driven<W: Word>(circuit: &Circuit<W>, lines: &[&str], wire: &str) -> Result<(), ParseError> {
+    if circuit.drives(wire) {
+        return Ok(());
+    }
+
+    let last = lines.last().copied().unwrap_or_default();
+    Err(ParseError::new(
+        Day07::DAY,
+        last,
+        &last[last.len()..],
+        format!("no component drives wire '{wire}'"),
+    )
+    .on_line(lines.len().max(1)))
+}
+
+/// Unsigned integer carrying the signal on a wire, its number of bits being the width of the circuit.
+pub trait Word:
+    Copy
+    + Eq
+    + Hash
+    + Debug
+    + Display
+    + FromStr
+    + BitAnd<Output = Self>
+    + BitOr<Output = Self>
+    + Not<Output = Self>
+{
+    const BITS: u32;
+
+    /// Shifts left, shifting out every bit when shifting by the width of the word or more.
+    fn shift_left(self, by: u32) -> Self;
+
+    /// Shifts right, shifting out every bit when shifting by the width of the word or more.
+    fn shift_right(self, by: u32) -> Self;
+}
+
+macro_rules! word {
+    ($($t:ty),*) => {
+        $(
+            impl Word for $t {
+                const BITS: u32 = <$t>::BITS;
+
+                fn shift_left(self, by: u32) -> Self {
+                    self.checked_shl(by).unwrap_or(0)
+                }
+
+                fn shift_right(self, by: u32) -> Self {
+                    self.checked_shr(by).unwrap_or(0)
+                }
+            }
+        )*
     };
+}
+
+word!(u8, u16, u32, u64);
+
+/// A validated circuit of `W` sized signals, its components sorted so every wire comes after
+/// all the wires it depends on.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Circuit<'a, W: Word = u16> {
+    components: Vec<Component<'a>>,
+    /// Wires no component drives, their signal coming from outside the circuit.
+    inputs: Vec<&'a str>,
+    width: PhantomData<W>,
+}
+
+impl<'a, W: Word> Circuit<'a, W> {
+    /// Builds the circuit, rejecting wires that are driven by no or by multiple components,
+    /// signals that do not fit in `W`, and circuits in which a wire (indirectly) depends on itself.
+    pub fn new(components: Vec<Component<'a>>) -> Result<Self, CircuitError<'a>> {
+        Self::with_inputs(components, &[])
+    }
+
+    /// Builds the circuit like [`Circuit::new`], except that the wires in `inputs` may be left undriven.
+    /// Those wires then need a signal when computing the signals of the circuit.
+    pub fn with_inputs(
+        components: Vec<Component<'a>>,
+        inputs: &[&str],
+    ) -> Result<Self, CircuitError<'a>> {
+        let mut drivers: FxHashMap<&str, usize> = FxHashMap::default();
+        for (idx, component) in components.iter().enumerate() {
+            let wire = component.destination();
+            if drivers.insert(wire, idx).is_some() {
+                return Err(CircuitError::MultiplyDriven {
+                    wire,
+                    component: idx,
+                });
+            }
+        }
+
+        let mut dependents: FxHashMap<&str, Vec<usize>> = FxHashMap::default();
+        let mut pending = vec![0; components.len()];
+        let mut undriven = vec![];
+        for (idx, component) in components.iter().enumerate() {
+            if let Some(signal) = component
+                .sources()
+                .find(|&s| is_signal(s) && W::from_str(s).is_err())
+            {
+                return Err(CircuitError::TooWide {
+                    signal,
+                    bits: W::BITS,
+                    component: idx,
+                });
+            }
+
+            for wire in component.sources().filter(|s| is_signal(s).not()) {
+                if drivers.contains_key(wire).not() {
+                    if inputs.contains(&wire) {
+                        undriven.push(wire);
+                        continue;
+                    }
+                    return Err(CircuitError::Undriven {
+                        wire,
+                        component: idx,
+                    });
+                }
+                dependents.entry(wire).or_default().push(idx);
+                pending[idx] += 1;
+            }
+        }
+
+        let mut ready: VecDeque<usize> =
+            (0..components.len()).filter(|&c| pending[c] == 0).collect();
+        let mut order = Vec::with_capacity(components.len());
+        while let Some(idx) = ready.pop_front() {
+            order.push(idx);
+            for &dependent in dependents
+                .get(components[idx].destination())
+                .into_iter()
+                .flatten()
+            {
+                pending[dependent] -= 1;
+                if pending[dependent] == 0 {
+                    ready.push_back(dependent);
+                }
+            }
+        }
 
-    ch.insert(wire, result);
+        if order.len() < components.len() {
+            return Err(Self::cycle(&components, &drivers, &pending));
+        }
+
+        Ok(Self {
+            components: order.into_iter().map(|idx| components[idx]).collect(),
+            inputs: undriven.into_iter().sorted().dedup().collect(),
+            width: PhantomData,
+        })
+    }
+
+    /// Finds the cycle keeping the topological sort from completing, starting from the first
+    /// component that could not be sorted and following its unsorted sources until a wire repeats.
+    fn cycle(
+        components: &[Component<'a>],
+        drivers: &FxHashMap<&str, usize>,
+        pending: &[usize],
+    ) -> CircuitError<'a> {
+        let mut idx = pending.iter().position(|&p| p > 0).unwrap();
+        let mut path: Vec<usize> = vec![];
+
+        while path.contains(&idx).not() {
+            path.push(idx);
+            idx = components[idx]
+                .sources()
+                .filter_map(|s| drivers.get(s).copied())
+                .find(|&d| pending[d] > 0)
+                .unwrap();
+        }
+
+        let start = path.iter().position(|&p| p == idx).unwrap();
+        CircuitError::Cycle {
+            wires: path[start..]
+                .iter()
+                .map(|&c| components[c].destination())
+                .collect(),
+            component: idx,
+        }
+    }
+
+    pub fn drives(&self, wire: &str) -> bool {
+        self.components.iter().any(|c| c.destination() == wire)
+    }
+
+    /// Computes the signal on every wire, wires in `overrides` carrying the given signal
+    /// instead of the signal of their component.
+    ///
+    /// Every input of the circuit needs an override, and every override needs a wire in the circuit.
+    pub fn signals<'o>(
+        &self,
+        overrides: &FxHashMap<&'o str, W>,
+    ) -> Result<FxHashMap<&'a str, W>, CircuitError<'o>>
+    where
+        'a: 'o,
+    {
+        if let Some(&wire) = overrides
+            .keys()
+            .sorted()
+            .find(|&&w| self.drives(w).not() && self.inputs.contains(&w).not())
+        {
+            return Err(CircuitError::Unknown { wire });
+        }
+
+        let mut signals: FxHashMap<&'a str, W> = FxHashMap::default();
+        for &wire in &self.inputs {
+            let signal = overrides.get(wire).ok_or(CircuitError::Unset { wire })?;
+            signals.insert(wire, *signal);
+        }
+
+        for component in &self.components {
+            if let Some(&signal) = overrides.get(component.destination()) {
+                signals.insert(component.destination(), signal);
+                continue;
+            }
+
+            let result = component.output(|wire| match is_signal(wire) {
+                true => W::from_str(wire).ok().unwrap(),
+                false => signals[wire],
+            });
+
+            signals.insert(component.destination(), result);
+        }
+
+        Ok(signals)
+    }
+
+    /// Simplifies the part of the circuit driving `target` into a netlist in the puzzle syntax.
+    ///
+    /// Gates fed by signals only are folded into a signal, plain wires are collapsed into
+    /// the wire driving them, and components not driving `target` are left out.
+    /// The wires in `inputs` are left undriven, so the netlist shows how `target` depends on them.
+    pub fn simplify(&self, target: &str, inputs: &[&str]) -> Option<String> {
+        let mut operands: FxHashMap<&'a str, Operand<'a, W>> = FxHashMap::default();
+        let mut gates: FxHashMap<&'a str, (String, Vec<&'a str>)> = FxHashMap::default();
+        let mut order = vec![];
+
+        for &wire in &self.inputs {
+            operands.insert(wire, Operand::Wire(wire));
+        }
+        for component in &self.components {
+            let wire = component.destination();
+            if inputs.contains(&wire) {
+                operands.insert(wire, Operand::Wire(wire));
+                continue;
+            }
+
+            let operand = |source: &str| match is_signal(source) {
+                true => Operand::Signal(W::from_str(source).ok().unwrap()),
+                false => operands[source],
+            };
+            let sources = component.sources().map(operand).collect_vec();
+
+            let folded = match sources.iter().all(|o| matches!(o, Operand::Signal(_))) {
+                true => Operand::Signal(component.output(|source| match operand(source) {
+                    Operand::Signal(signal) => signal,
+                    Operand::Wire(_) => unreachable!(),
+                })),
+                false => match component {
+                    Component::Wire { .. } => sources[0],
+                    _ => {
+                        let line = match (component, &sources[..]) {
+                            (Component::Not { .. }, [a]) => format!("NOT {a} -> {wire}"),
+                            (_, [a]) => format!("{a} {} -> {wire}", component.gate().unwrap()),
+                            (_, [a, b]) => {
+                                format!("{a} {} {b} -> {wire}", component.gate().unwrap())
+                            }
+                            _ => unreachable!(),
+                        };
+                        let wires = sources.iter().filter_map(|o| o.wire()).collect();
+
+                        gates.insert(wire, (line, wires));
+                        order.push(wire);
+                        Operand::Wire(wire)
+                    }
+                },
+            };
+
+            operands.insert(wire, folded);
+        }
+
+        let driver = *operands.get(target)?;
+        let mut reachable: FxHashSet<&str> = FxHashSet::default();
+        let mut todo = driver.wire().into_iter().collect_vec();
+        while let Some(wire) = todo.pop() {
+            if reachable.insert(wire) {
+                todo.extend(gates.get(wire).into_iter().flat_map(|(_, w)| w));
+            }
+        }
+
+        let alias = match driver {
+            Operand::Wire(wire) if wire == target => None,
+            driver => Some(format!("{driver} -> {target}")),
+        };
+
+        Some(
+            order
+                .into_iter()
+                .filter(|wire| reachable.contains(wire))
+                .map(|wire| gates[wire].0.clone())
+                .chain(alias)
+                .join("\n"),
+        )
+    }
+
+    /// Draws the circuit as a Graphviz DOT graph, wires being ellipses and gates being boxes.
+    ///
+    /// When `signals` are given, every wire is labelled with its signal as well.
+    pub fn dot(&self, signals: Option<&FxHashMap<&str, W>>) -> String {
+        let mut dot = vec![
+            "digraph circuit {".to_string(),
+            "    rankdir=LR;".to_string(),
+            "    node [shape=ellipse];".to_string(),
+        ];
+
+        for (idx, component) in self.components.iter().enumerate() {
+            let wire = component.destination();
+            let label = match signals.and_then(|s| s.get(wire)) {
+                Some(signal) => format!("{wire}\\n{signal}"),
+                None => wire.to_string(),
+            };
+            dot.push(format!("    \"{wire}\" [label=\"{label}\"];"));
+
+            let target = match component.gate() {
+                Some(gate) => {
+                    let node = format!("{idx}:{gate}");
+                    dot.push(format!("    \"{node}\" [shape=box, label=\"{gate}\"];"));
+                    dot.push(format!("    \"{node}\" -> \"{wire}\";"));
+                    node
+                }
+                None => wire.to_string(),
+            };
+
+            for source in component.sources() {
+                let node = match is_signal(source) {
+                    true => {
+                        let node = format!("{idx}:{source}");
+                        dot.push(format!(
+                            "    \"{node}\" [shape=plaintext, label=\"{source}\"];"
+                        ));
+                        node
+                    }
+                    false => source.to_string(),
+                };
+                dot.push(format!("    \"{node}\" -> \"{target}\";"));
+            }
+        }
 
-    result
+        dot.push("}".to_string());
+        dot.join("\n")
+    }
 }
 
+/// Source of a gate in a simplified circuit, either a known signal or a wire.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum Operand<'a, W> {
+    Signal(W),
+    Wire(&'a str),
+}
+
+impl<'a, W> Operand<'a, W> {
+    fn wire(&self) -> Option<&'a str> {
+        match self {
+            Operand::Signal(_) => None,
+            Operand::Wire(wire) => Some(wire),
+        }
+    }
+}
+
+impl<W: Display> Display for Operand<'_, W> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Operand::Signal(signal) => write!(f, "{signal}"),
+            Operand::Wire(wire) => write!(f, "{wire}"),
+        }
+    }
+}
+
+/// Sources made up of digits are signals, any other source is a wire.
+fn is_signal(source: &str) -> bool {
+    source.bytes().all(|b| b.is_ascii_digit())
+}
+
+/// Reason a list of components does not form a valid [`Circuit`].
+///
+/// Most variants hold the index of the offending component, `wire` pointing into its text.
+/// The others are about signals given to the circuit, rather than about its components.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum CircuitError<'a> {
+    Cycle {
+        wires: Vec<&'a str>,
+        component: usize,
+    },
+    Undriven {
+        wire: &'a str,
+        component: usize,
+    },
+    MultiplyDriven {
+        wire: &'a str,
+        component: usize,
+    },
+    TooWide {
+        signal: &'a str,
+        bits: u32,
+        component: usize,
+    },
+    /// A signal is given for a wire that is not in the circuit.
+    Unknown {
+        wire: &'a str,
+    },
+    /// No signal is given for an input of the circuit.
+    Unset {
+        wire: &'a str,
+    },
+}
+
+impl<'a> CircuitError<'a> {
+    /// Points the error at the line of the offending component, `lines` being the parsed text.
+    /// Errors about given signals point at the end of the text.
+    pub fn into_parse_error(self, lines: &[&str]) -> ParseError {
+        let (wire, component) = match &self {
+            CircuitError::Unknown { .. } | CircuitError::Unset { .. } => {
+                let last = lines.last().copied().unwrap_or_default();
+                return ParseError::new(Day07::DAY, last, &last[last.len()..], self.to_string())
+                    .on_line(lines.len().max(1));
+            }
+            CircuitError::Cycle { wires, component } => (wires[0], *component),
+            CircuitError::Undriven { wire, component } => (*wire, *component),
+            CircuitError::MultiplyDriven { wire, component } => (*wire, *component),
+            CircuitError::TooWide {
+                signal, component, ..
+            } => (*signal, *component),
+        };
+
+        ParseError::new(Day07::DAY, lines[component], wire, self.to_string()).on_line(component + 1)
+    }
+}
+
+impl Display for CircuitError<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CircuitError::Cycle { wires, .. } => write!(
+                f,
+                "wires form a cycle: {} -> {}",
+                wires[..1]
+                    .iter()
+                    .chain(wires[1..].iter().rev())
+                    .join(" -> "),
+                wires[0]
+            ),
+            CircuitError::Undriven { wire, .. } => {
+                write!(f, "wire '{wire}' is not driven by any component")
+            }
+            CircuitError::MultiplyDriven { wire, .. } => {
+                write!(f, "wire '{wire}' is driven by more than one component")
+            }
+            CircuitError::TooWide { signal, bits, .. } => {
+                write!(f, "signal '{signal}' does not fit in {bits} bits")
+            }
+            CircuitError::Unknown { wire } => {
+                write!(f, "wire '{wire}' is not in the circuit")
+            }
+            CircuitError::Unset { wire } => {
+                write!(f, "wire '{wire}' is an input without a signal")
+            }
+        }
+    }
+}
+
+impl Error for CircuitError<'_> {}
+
 lazy_static! {
     static ref RE_WIRE: Regex = Regex::new(r"^(?<source>\w+) -> (?<destination>\w+)$").unwrap();
     static ref RE_AND: Regex =
@@ -73,7 +571,7 @@ lazy_static! {
 }
 
 #[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
-enum Component<'a> {
+pub enum Component<'a> {
     Wire {
         source: &'a str,
         destination: &'a str,
@@ -105,8 +603,8 @@ enum Component<'a> {
 }
 
 impl<'a> Component<'a> {
-    fn new(component: &'a str) -> Self {
-        match component {
+    fn new(component: &'a str) -> Result<Self, ParseError> {
+        let component = match component {
             c if RE_WIRE.is_match(c) => {
                 let caps = RE_WIRE.captures(c).unwrap();
 
@@ -159,7 +657,7 @@ impl<'a> Component<'a> {
                 let caps = RE_LSHIFT.captures(c).unwrap();
 
                 let source = caps.name("source").unwrap().as_str();
-                let value = u16::from_str(caps.name("value").unwrap().as_str()).unwrap();
+                let value = parse_number(Day07::DAY, c, caps.name("value").unwrap().as_str())?;
                 let destination = caps.name("destination").unwrap().as_str();
 
                 Component::LShift {
@@ -172,7 +670,7 @@ impl<'a> Component<'a> {
                 let caps = RE_RSHIFT.captures(c).unwrap();
 
                 let source = caps.name("source").unwrap().as_str();
-                let value = u16::from_str(caps.name("value").unwrap().as_str()).unwrap();
+                let value = parse_number(Day07::DAY, c, caps.name("value").unwrap().as_str())?;
                 let destination = caps.name("destination").unwrap().as_str();
 
                 Component::RShift {
@@ -181,7 +679,62 @@ impl<'a> Component<'a> {
                     destination,
                 }
             }
-            _ => unreachable!(),
+            c => {
+                return Err(ParseError::new(
+                    Day07::DAY,
+                    c,
+                    c,
+                    "expected a wire, gate or signal driving '-> destination'",
+                ))
+            }
+        };
+
+        Ok(component)
+    }
+
+    /// Wires or signals feeding into this component.
+    fn sources(&self) -> impl Iterator<Item = &'a str> {
+        let (a, b) = match *self {
+            Component::Wire { source, .. } => (source, None),
+            Component::And {
+                source_a, source_b, ..
+            } => (source_a, Some(source_b)),
+            Component::Or {
+                source_a, source_b, ..
+            } => (source_a, Some(source_b)),
+            Component::Not { source, .. } => (source, None),
+            Component::LShift { source, .. } => (source, None),
+            Component::RShift { source, .. } => (source, None),
+        };
+
+        [Some(a), b].into_iter().flatten()
+    }
+
+    /// Signal on the destination, given the signal on every source.
+    fn output<W: Word>(&self, signal: impl Fn(&str) -> W) -> W {
+        match *self {
+            Component::Wire { source, .. } => signal(source),
+            Component::And {
+                source_a, source_b, ..
+            } => signal(source_a) & signal(source_b),
+            Component::Or {
+                source_a, source_b, ..
+            } => signal(source_a) | signal(source_b),
+            Component::Not { source, .. } => !signal(source),
+            Component::LShift { source, value, .. } => signal(source).shift_left(value as u32),
+            Component::RShift { source, value, .. } => signal(source).shift_right(value as u32),
+        }
+    }
+
+    /// Label of the gate, plain wires having none.
+    fn gate(&self) -> Option<String> {
+        match self {
+            Component::Wire { .. } => None,
+            Component::And { .. } => Some("AND".to_string()),
+            Component::Or { .. } => Some("OR".to_string()),
+            Component::Not { .. } => Some("NOT".to_string()),
+            Component::LShift { value, .. } => Some(format!("LSHIFT {value}")),
+            Component::RShift { value, .. } => Some(format!("RSHIFT {value}")),
         }
     }
 
@@ -197,11 +750,36 @@ impl<'a> Component<'a> {
     }
 }
 
+pub struct Day07;
+
+impl Solution for Day07 {
+    const DAY: u8 = 7;
+
+    type Input<'a> = Circuit<'a>;
+    type Answer1 = u16;
+    type Answer2 = u16;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        let lines = input.lines().collect_vec();
+        let circuit = parse(&lines)?;
+        driven(&circuit, &lines, "b")?;
+
+        Ok(circuit)
+    }
+
+    fn part_1(input: &Self::Input<'_>) -> Self::Answer1 {
+        simulate(input, false)
+    }
+
+    fn part_2(input: &Self::Input<'_>) -> Self::Answer2 {
+        simulate(input, true)
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use itertools::Itertools;
-
     use super::*;
+    use crate::util::Input;
 
     #[test]
     fn day_07_part_01_sample() {
@@ -216,16 +794,14 @@ mod tests {
             "NOT y -> i",
         ];
 
-        assert_eq!(492, solve_1(&sample));
+        assert_eq!(Ok(492), solve_1(&sample));
     }
 
     #[test]
     fn day_07_part_01_solution() {
-        let input = include_str!("../../inputs/day_07.txt")
-            .lines()
-            .collect_vec();
+        let input = Input::load(7).unwrap().lines();
 
-        assert_eq!(46_065, solve_1(&input));
+        assert_eq!(Ok(46_065), solve_1(&input));
     }
 
     #[test]
@@ -235,10 +811,199 @@ mod tests {
 
     #[test]
     fn day_07_part_02_solution() {
-        let input = include_str!("../../inputs/day_07.txt")
-            .lines()
+        let input = Input::load(7).unwrap().lines();
+
+        assert_eq!(Ok(14_134), solve_2(&input));
+    }
+
+    #[test]
+    fn day_07_parse_error() {
+        let error = solve_1(&["123 -> x", "x XOR y -> a"]).unwrap_err();
+
+        assert_eq!((2, 1), (error.line, error.column));
+
+        let error = solve_1(&["x LSHIFT 99999 -> a"]).unwrap_err();
+
+        assert_eq!((1, 10), (error.line, error.column));
+    }
+
+    #[test]
+    fn day_07_signals() {
+        let sample = vec![
+            "123 -> x",
+            "456 -> y",
+            "x AND y -> d",
+            "x OR y -> e",
+            "x LSHIFT 2 -> f",
+            "y RSHIFT 2 -> g",
+            "NOT x -> h",
+            "NOT y -> i",
+        ];
+        let signals = |overrides: &[(&str, u16)]| {
+            signals(&sample, &FxHashMap::from_iter(overrides.iter().copied()))
+                .unwrap()
+                .into_iter()
+                .sorted()
+                .collect_vec()
+        };
+
+        assert_eq!(
+            vec![
+                ("d", 72),
+                ("e", 507),
+                ("f", 492),
+                ("g", 114),
+                ("h", 65_412),
+                ("i", 65_079),
+                ("x", 123),
+                ("y", 456),
+            ],
+            signals(&[])
+        );
+        assert_eq!(
+            vec![
+                ("d", 0),
+                ("e", 456),
+                ("f", 0),
+                ("g", 114),
+                ("h", 65_535),
+                ("i", 65_079),
+                ("x", 0),
+                ("y", 456),
+            ],
+            signals(&[("x", 0)])
+        );
+
+        let error = super::signals(&sample, &FxHashMap::from_iter([("z", 1_u16)])).unwrap_err();
+        assert_eq!((8, 11), (error.line, error.column));
+        assert_eq!("wire 'z' is not in the circuit", error.reason);
+
+        let circuit = ["x AND y -> a", "NOT y -> b"];
+        let signals = super::signals(&circuit, &FxHashMap::from_iter([("x", 12_u16), ("y", 10)]));
+        assert_eq!(Ok(8), signals.map(|s| s["a"]));
+
+        let error = super::signals(&circuit, &FxHashMap::from_iter([("x", 12_u16)])).unwrap_err();
+        assert_eq!("wire 'y' is not driven by any component", error.reason);
+        let circuit = Circuit::<u16>::with_inputs(
+            parse_lines(&circuit, Component::new).unwrap(),
+            &["x", "y"],
+        )
+        .unwrap();
+        assert_eq!(
+            Err(CircuitError::Unset { wire: "y" }),
+            circuit.signals(&FxHashMap::from_iter([("x", 12)]))
+        );
+    }
+
+    #[test]
+    fn day_07_dot() {
+        let sample = vec!["x AND 1 -> a", "3 -> x", "a LSHIFT 2 -> b"];
+
+        assert_eq!(
+            "digraph circuit {\n    \
+                 rankdir=LR;\n    \
+                 node [shape=ellipse];\n    \
+                 \"x\" [label=\"x\\n3\"];\n    \
+                 \"0:3\" [shape=plaintext, label=\"3\"];\n    \
+                 \"0:3\" -> \"x\";\n    \
+                 \"a\" [label=\"a\\n1\"];\n    \
+                 \"1:AND\" [shape=box, label=\"AND\"];\n    \
+                 \"1:AND\" -> \"a\";\n    \
+                 \"x\" -> \"1:AND\";\n    \
+                 \"1:1\" [shape=plaintext, label=\"1\"];\n    \
+                 \"1:1\" -> \"1:AND\";\n    \
+                 \"b\" [label=\"b\\n4\"];\n    \
+                 \"2:LSHIFT 2\" [shape=box, label=\"LSHIFT 2\"];\n    \
+                 \"2:LSHIFT 2\" -> \"b\";\n    \
+                 \"a\" -> \"2:LSHIFT 2\";\n\
+             }",
+            dot(&sample, true).unwrap()
+        );
+    }
+
+    #[test]
+    fn day_07_bit_width() {
+        let sample = vec![
+            "200 -> x",
+            "NOT x -> y",
+            "x LSHIFT 1 -> z",
+            "x RSHIFT 8 -> w",
+        ];
+
+        let narrow = signals::<u8>(&sample, &FxHashMap::default()).unwrap();
+        assert_eq!((55, 144, 0), (narrow["y"], narrow["z"], narrow["w"]));
+
+        let wide = signals::<u64>(&sample, &FxHashMap::default()).unwrap();
+        assert_eq!((u64::MAX - 200, 400, 0), (wide["y"], wide["z"], wide["w"]));
+
+        let shifted = signals::<u32>(&["1 -> x", "x LSHIFT 40 -> a"], &FxHashMap::default());
+        assert_eq!(0, shifted.unwrap()["a"]);
+
+        let error =
+            signals::<u8>(&["1 -> x", "300 AND x -> a"], &FxHashMap::default()).unwrap_err();
+        assert_eq!((2, 1), (error.line, error.column));
+        assert_eq!("signal '300' does not fit in 8 bits", error.reason);
+    }
+
+    #[test]
+    fn day_07_simplify() {
+        let sample = vec![
+            "123 -> x",
+            "456 -> y",
+            "x AND y -> d",
+            "b -> c",
+            "c OR d -> f",
+            "f LSHIFT 1 -> g",
+            "g -> a",
+            "NOT x -> h",
+            "1 -> b",
+        ];
+
+        assert_eq!(Ok("146 -> a".to_string()), simplify(&sample, "a", &[]));
+        assert_eq!(
+            Ok("b OR 72 -> f\nf LSHIFT 1 -> g\ng -> a".to_string()),
+            simplify(&sample, "a", &["b"])
+        );
+        assert_eq!(Ok("".to_string()), simplify(&sample, "b", &["b"]));
+        assert!(simplify(&sample, "z", &[]).is_err());
+    }
+
+    #[test]
+    fn day_07_deep_circuit() {
+        let circuit = (1..10_000)
+            .map(|w| format!("w{} -> w{w}", w - 1))
+            .chain(["7 -> w0".to_string(), "w9999 -> a".to_string()])
             .collect_vec();
 
-        assert_eq!(14_134, solve_2(&input));
+        assert_eq!(
+            Ok(7),
+            solve_1(&circuit.iter().map(|c| c.as_str()).collect_vec())
+        );
+    }
+
+    #[test]
+    fn day_07_circuit_error() {
+        let error = solve_1(&["x -> a", "NOT y -> x", "a AND 1 -> y"]).unwrap_err();
+
+        assert_eq!((1, 6), (error.line, error.column));
+        assert_eq!("wires form a cycle: a -> y -> x -> a", error.reason);
+
+        let error = solve_1(&["1 -> x", "x OR y -> a"]).unwrap_err();
+
+        assert_eq!((2, 6), (error.line, error.column));
+        assert_eq!("wire 'y' is not driven by any component", error.reason);
+
+        let error = solve_1(&["1 -> a", "2 -> a"]).unwrap_err();
+
+        assert_eq!((2, 6), (error.line, error.column));
+        assert_eq!(
+            "wire 'a' is driven by more than one component",
+            error.reason
+        );
+
+        let error = solve_1(&["1 -> x"]).unwrap_err();
+
+        assert_eq!((1, 7), (error.line, error.column));
+        assert_eq!("no component drives wire 'a'", error.reason);
     }
 }
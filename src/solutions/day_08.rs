@@ -1,6 +1,18 @@
+use itertools::Itertools;
 use regex::Regex;
 
-pub fn solve_1(strings: &[&str]) -> usize {
+use crate::error::{parse_lines, ParseError};
+use crate::solutions::Solution;
+
+pub fn solve_1(strings: &[&str]) -> Result<usize, ParseError> {
+    Ok(decoded_overhead(&parse_lines(strings, parse)?))
+}
+
+pub fn solve_2(strings: &[&str]) -> Result<usize, ParseError> {
+    Ok(encoded_overhead(&parse_lines(strings, parse)?))
+}
+
+fn decoded_overhead(strings: &[&str]) -> usize {
     let re = Regex::new(r"(\\x[0-9a-f]{2})").unwrap();
 
     let code_characters: usize = strings.iter().map(|s| s.len()).sum();
@@ -16,48 +28,85 @@ pub fn solve_1(strings: &[&str]) -> usize {
     code_characters - memory_characters
 }
 
-pub fn solve_2(strings: &[&str]) -> usize {
+fn encoded_overhead(strings: &[&str]) -> usize {
     strings
         .iter()
         .map(|s| s.matches('\\').count() + s.matches('\"').count() + 2)
         .sum()
 }
 
+fn parse(string: &str) -> Result<&str, ParseError> {
+    if string.len() >= 2 && string.starts_with('"') && string.ends_with('"') {
+        Ok(string)
+    } else {
+        Err(ParseError::new(
+            Day08::DAY,
+            string,
+            string,
+            "expected a double quoted string literal",
+        ))
+    }
+}
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    const DAY: u8 = 8;
+
+    type Input<'a> = Vec<&'a str>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_lines(&input.lines().collect_vec(), parse)
+    }
+
+    fn part_1(input: &Self::Input<'_>) -> Self::Answer1 {
+        decoded_overhead(input)
+    }
+
+    fn part_2(input: &Self::Input<'_>) -> Self::Answer2 {
+        encoded_overhead(input)
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use itertools::Itertools;
-
     use super::*;
+    use crate::util::Input;
 
     #[test]
     fn day_08_part_01_sample() {
         let sample = vec!["\"\"", "\"abc\"", "\"aaa\\\"aaa\"", "\"\\x27\""];
 
-        assert_eq!(12, solve_1(&sample));
+        assert_eq!(Ok(12), solve_1(&sample));
     }
 
     #[test]
     fn day_08_part_01_solution() {
-        let input = include_str!("../../inputs/day_08.txt")
-            .lines()
-            .collect_vec();
+        let input = Input::load(8).unwrap().lines();
 
-        assert_eq!(1_333, solve_1(&input));
+        assert_eq!(Ok(1_333), solve_1(&input));
     }
 
     #[test]
     fn day_08_part_02_sample() {
         let sample = vec!["\"\"", "\"abc\"", "\"aaa\\\"aaa\"", "\"\\x27\""];
 
-        assert_eq!(19, solve_2(&sample));
+        assert_eq!(Ok(19), solve_2(&sample));
     }
 
     #[test]
     fn day_08_part_02_solution() {
-        let input = include_str!("../../inputs/day_08.txt")
-            .lines()
-            .collect_vec();
+        let input = Input::load(8).unwrap().lines();
+
+        assert_eq!(Ok(2_046), solve_2(&input));
+    }
+
+    #[test]
+    fn day_08_parse_error() {
+        let error = solve_1(&["\"\"", "\"abc"]).unwrap_err();
 
-        assert_eq!(2_046, solve_2(&input));
+        assert_eq!((2, 1), (error.line, error.column));
     }
 }
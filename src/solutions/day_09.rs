@@ -1,20 +1,27 @@
-use std::str::FromStr;
-
 use itertools::Itertools;
 use regex::Regex;
 use rustc_hash::{FxHashMap, FxHashSet};
 
-pub fn solve_1(distances: &[&str]) -> u16 {
-    solve(distances).into_iter().min().unwrap()
+use crate::error::{parse_lines, parse_number, ParseError};
+use crate::solutions::Solution;
+
+pub fn solve_1(distances: &[&str]) -> Result<u16, ParseError> {
+    Ok(shortest(&Graph::new(distances)?))
 }
 
-pub fn solve_2(distances: &[&str]) -> u16 {
-    solve(distances).into_iter().max().unwrap()
+pub fn solve_2(distances: &[&str]) -> Result<u16, ParseError> {
+    Ok(longest(&Graph::new(distances)?))
 }
 
-fn solve(distances: &[&str]) -> Vec<u16> {
-    let graph = Graph::new(distances);
+fn shortest(graph: &Graph) -> u16 {
+    routes(graph).into_iter().min().unwrap()
+}
+
+fn longest(graph: &Graph) -> u16 {
+    routes(graph).into_iter().max().unwrap()
+}
 
+fn routes(graph: &Graph) -> Vec<u16> {
     graph
         .nodes
         .iter()
@@ -28,38 +35,61 @@ fn solve(distances: &[&str]) -> Vec<u16> {
 }
 
 #[derive(Debug)]
-struct Graph<'a> {
+pub struct Graph<'a> {
     nodes: FxHashSet<&'a str>,
     vertices: FxHashMap<(&'a str, &'a str), u16>,
 }
 
 impl<'a> Graph<'a> {
-    fn new(distances: &[&'a str]) -> Self {
+    fn new(distances: &[&'a str]) -> Result<Self, ParseError> {
         let re = Regex::new(r"^(?<city_a>\w+) to (?<city_b>\w+) = (?<distance>\d+)$").unwrap();
 
-        let vertices: FxHashMap<(&'a str, &'a str), u16> = distances
-            .iter()
-            .flat_map(|d| {
-                let caps = re.captures(d).unwrap();
+        let vertices: FxHashMap<(&'a str, &'a str), u16> = parse_lines(distances, |d| {
+            let caps = re.captures(d).ok_or_else(|| {
+                ParseError::new(Day09::DAY, d, d, "expected 'CITY to CITY = DISTANCE'")
+            })?;
 
-                let city_a = caps.name("city_a").unwrap().as_str();
-                let city_b = caps.name("city_b").unwrap().as_str();
-                let distance = u16::from_str(caps.name("distance").unwrap().as_str()).unwrap();
+            let city_a = caps.name("city_a").unwrap().as_str();
+            let city_b = caps.name("city_b").unwrap().as_str();
+            let distance = parse_number(Day09::DAY, d, caps.name("distance").unwrap().as_str())?;
 
-                [((city_a, city_b), distance), ((city_b, city_a), distance)]
-            })
-            .collect();
+            Ok([((city_a, city_b), distance), ((city_b, city_a), distance)])
+        })?
+        .into_iter()
+        .flatten()
+        .collect();
         let nodes = vertices.keys().map(|(a, _)| *a).collect();
 
-        Self { vertices, nodes }
+        Ok(Self { vertices, nodes })
+    }
+}
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    const DAY: u8 = 9;
+
+    type Input<'a> = Graph<'a>;
+    type Answer1 = u16;
+    type Answer2 = u16;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        Graph::new(&input.lines().collect_vec())
+    }
+
+    fn part_1(input: &Self::Input<'_>) -> Self::Answer1 {
+        shortest(input)
+    }
+
+    fn part_2(input: &Self::Input<'_>) -> Self::Answer2 {
+        longest(input)
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use itertools::Itertools;
-
     use super::*;
+    use crate::util::Input;
 
     #[test]
     fn day_09_part_01_sample() {
@@ -69,16 +99,14 @@ mod tests {
             "Dublin to Belfast = 141",
         ];
 
-        assert_eq!(605, solve_1(&sample));
+        assert_eq!(Ok(605), solve_1(&sample));
     }
 
     #[test]
     fn day_09_part_01_solution() {
-        let input = include_str!("../../inputs/day_09.txt")
-            .lines()
-            .collect_vec();
+        let input = Input::load(9).unwrap().lines();
 
-        assert_eq!(251, solve_1(&input));
+        assert_eq!(Ok(251), solve_1(&input));
     }
 
     #[test]
@@ -89,15 +117,20 @@ mod tests {
             "Dublin to Belfast = 141",
         ];
 
-        assert_eq!(982, solve_2(&sample));
+        assert_eq!(Ok(982), solve_2(&sample));
     }
 
     #[test]
     fn day_09_part_02_solution() {
-        let input = include_str!("../../inputs/day_09.txt")
-            .lines()
-            .collect_vec();
+        let input = Input::load(9).unwrap().lines();
+
+        assert_eq!(Ok(898), solve_2(&input));
+    }
+
+    #[test]
+    fn day_09_parse_error() {
+        let error = solve_1(&["London to Dublin = 464", "London - Belfast = 518"]).unwrap_err();
 
-        assert_eq!(898, solve_2(&input));
+        assert_eq!((2, 1), (error.line, error.column));
     }
 }
@@ -1,17 +1,44 @@
+use crate::error::ParseError;
+use crate::solutions::Solution;
 use crate::util::BASE_10;
-use itertools::Itertools;
 
-pub fn solve(start: &str, nr_steps: u8) -> usize {
-    let mut number = start
-        .chars()
-        .map(|c| c.to_digit(BASE_10).unwrap() as u8)
-        .collect_vec();
+pub fn solve(start: &str, nr_steps: u8) -> Result<usize, ParseError> {
+    Ok(look_and_say(&parse(start)?, nr_steps))
+}
+
+fn look_and_say(start: &[u8], nr_steps: u8) -> usize {
+    let mut number = start.to_vec();
 
     (0..nr_steps).for_each(|_| number = expand(&number));
 
     number.len()
 }
 
+fn parse(start: &str) -> Result<Vec<u8>, ParseError> {
+    if start.is_empty() {
+        return Err(ParseError::new(
+            Day10::DAY,
+            start,
+            start,
+            "expected a number",
+        ));
+    }
+
+    start
+        .char_indices()
+        .map(|(idx, c)| {
+            c.to_digit(BASE_10).map(|d| d as u8).ok_or_else(|| {
+                ParseError::new(
+                    Day10::DAY,
+                    start,
+                    &start[idx..],
+                    format!("unexpected character '{c}'"),
+                )
+            })
+        })
+        .collect()
+}
+
 fn expand(number: &[u8]) -> Vec<u8> {
     let mut expanded = vec![];
 
@@ -36,22 +63,45 @@ fn expand(number: &[u8]) -> Vec<u8> {
     expanded
 }
 
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+
+    type Input<'a> = Vec<u8>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input.trim())
+    }
+
+    fn part_1(input: &Self::Input<'_>) -> Self::Answer1 {
+        look_and_say(input, 40)
+    }
+
+    fn part_2(input: &Self::Input<'_>) -> Self::Answer2 {
+        look_and_say(input, 50)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::util::Input;
 
     #[test]
     fn day_10_part_01_sample() {
         let sample = "1";
 
-        assert_eq!(6, solve(sample, 5));
+        assert_eq!(Ok(6), solve(sample, 5));
     }
 
     #[test]
     fn day_10_part_01_solution() {
-        let input = include_str!("../../inputs/day_10.txt").trim();
+        let input = Input::load(10).unwrap().text();
 
-        assert_eq!(252_594, solve(input, 40));
+        assert_eq!(Ok(252_594), solve(input, 40));
     }
 
     #[test]
@@ -61,8 +111,15 @@ mod tests {
 
     #[test]
     fn day_10_part_02_solution() {
-        let input = include_str!("../../inputs/day_10.txt").trim();
+        let input = Input::load(10).unwrap().text();
+
+        assert_eq!(Ok(3_579_328), solve(input, 50));
+    }
+
+    #[test]
+    fn day_10_parse_error() {
+        let error = solve("1121a", 1).unwrap_err();
 
-        assert_eq!(3_579_328, solve(input, 50));
+        assert_eq!((1, 5), (error.line, error.column));
     }
 }
@@ -1,28 +1,60 @@
+use std::fmt::{Display, Formatter};
 use std::ops::Not;
 
 use itertools::Itertools;
 
-pub fn solve(password: &str) -> String {
-    let mut password = Password::new(password);
+use crate::answer::Answer;
+use crate::error::ParseError;
+use crate::solutions::Solution;
+
+pub fn solve(password: &str) -> Result<String, ParseError> {
+    Ok(next(&Password::new(password)?).to_string())
+}
+
+fn next(password: &Password) -> Password {
+    let mut password = password.clone();
     password.increment();
 
     while password.is_valid().not() {
         password.increment();
     }
 
-    String::from_utf8(password.text).unwrap()
+    password
 }
 
-#[derive(Debug)]
-struct Password {
+/// Length of the shortest valid password, such as `aabcc`, shorter passwords never becoming valid.
+const MIN_LENGTH: usize = 5;
+
+#[derive(Debug, Clone)]
+pub struct Password {
     text: Vec<u8>,
 }
 
 impl Password {
-    fn new(password: &str) -> Self {
-        Password {
-            text: password.chars().map(|c| c as u8).collect_vec(),
+    fn new(password: &str) -> Result<Self, ParseError> {
+        if let Some((idx, c)) = password
+            .char_indices()
+            .find(|(_, c)| c.is_ascii_lowercase().not())
+        {
+            return Err(ParseError::new(
+                Day11::DAY,
+                password,
+                &password[idx..],
+                format!("unexpected character '{c}', expected lowercase letters only"),
+            ));
+        }
+        if password.len() < MIN_LENGTH {
+            return Err(ParseError::new(
+                Day11::DAY,
+                password,
+                password,
+                format!("expected at least {MIN_LENGTH} letters, no shorter password is valid"),
+            ));
         }
+
+        Ok(Password {
+            text: password.chars().map(|c| c as u8).collect_vec(),
+        })
     }
 
     fn increment(&mut self) {
@@ -64,21 +96,56 @@ impl Password {
     }
 }
 
+impl Display for Password {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", String::from_utf8_lossy(&self.text))
+    }
+}
+
+impl From<Password> for Answer {
+    fn from(password: Password) -> Self {
+        Answer::Text(password.to_string())
+    }
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u8 = 11;
+
+    type Input<'a> = Password;
+    type Answer1 = Password;
+    type Answer2 = Password;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        Password::new(input.trim())
+    }
+
+    fn part_1(input: &Self::Input<'_>) -> Self::Answer1 {
+        next(input)
+    }
+
+    fn part_2(input: &Self::Input<'_>) -> Self::Answer2 {
+        next(&next(input))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::util::Input;
 
     #[test]
     fn day_11_part_01_sample() {
-        assert_eq!("abcdffaa", solve("abcdefgh"));
-        assert_eq!("ghjaabcc", solve("ghijklmn"));
+        assert_eq!(Ok("abcdffaa".to_string()), solve("abcdefgh"));
+        assert_eq!(Ok("ghjaabcc".to_string()), solve("ghijklmn"));
     }
 
     #[test]
     fn day_11_part_01_solution() {
-        let input = include_str!("../../inputs/day_11.txt").trim();
+        let input = Input::load(11).unwrap().text();
 
-        assert_eq!("vzbxxyzz", solve(input));
+        assert_eq!(Ok("vzbxxyzz".to_string()), solve(input));
     }
 
     #[test]
@@ -88,8 +155,21 @@ mod tests {
 
     #[test]
     fn day_11_part_02_solution() {
-        let input = include_str!("../../inputs/day_11.txt").trim();
+        let input = Input::load(11).unwrap().text();
+
+        assert_eq!(
+            Ok("vzcaabcc".to_string()),
+            solve(input).and_then(|p| solve(&p))
+        );
+    }
+
+    #[test]
+    fn day_11_parse_error() {
+        let error = solve("abcDefgh").unwrap_err();
 
-        assert_eq!("vzcaabcc", solve(&solve(input)));
+        assert_eq!((1, 4), (error.line, error.column));
+        assert!(solve("").is_err());
+        assert!(solve("abcd").is_err());
+        assert_eq!(Ok("aabcc".to_string()), solve("aabcb"));
     }
 }
@@ -1,8 +1,22 @@
+use std::ops::Not;
 use std::str::FromStr;
 
+use lazy_static::lazy_static;
+use regex::Regex;
 use serde_json::Value;
 
-pub fn solve_1(json: &str) -> i64 {
+use crate::error::{json_error, ParseError};
+use crate::solutions::Solution;
+
+pub fn solve_1(json: &str) -> Result<i64, ParseError> {
+    Ok(sum_all(&parse(json)?))
+}
+
+pub fn solve_2(json: &str) -> Result<i64, ParseError> {
+    Ok(sum_non_red(&parse(json)?))
+}
+
+fn sum_all(json: &Value) -> i64 {
     fn sum(json: &Value) -> i64 {
         match json {
             Value::Null => 0,
@@ -14,11 +28,10 @@ pub fn solve_1(json: &str) -> i64 {
         }
     }
 
-    let json = Value::from_str(json).unwrap();
-    sum(&json)
+    sum(json)
 }
 
-pub fn solve_2(json: &str) -> i64 {
+fn sum_non_red(json: &Value) -> i64 {
     fn sum(json: &Value) -> i64 {
         match json {
             Value::Null => 0,
@@ -44,45 +57,121 @@ pub fn solve_2(json: &str) -> i64 {
         }
     }
 
-    let json = Value::from_str(json).unwrap();
-    sum(&json)
+    sum(json)
+}
+
+fn parse(json: &str) -> Result<Value, ParseError> {
+    let value = Value::from_str(json).map_err(|e| json_error(Day12::DAY, json, &e))?;
+    integers(json)?;
+
+    Ok(value)
+}
+
+/// Rejects numbers that are not integers fitting an `i64`, such as fractions, pointing at the first one.
+/// Strings never span lines in valid JSON, so skipping them line by line is enough.
+fn integers(json: &str) -> Result<(), ParseError> {
+    lazy_static! {
+        static ref RE_TOKEN: Regex = Regex::new(r#""(?:[^"\\]|\\.)*"|-?\d[\d.eE+-]*"#).unwrap();
+    }
+
+    for (idx, line) in json.lines().enumerate() {
+        let number = RE_TOKEN
+            .find_iter(line)
+            .map(|token| token.as_str())
+            .filter(|token| token.starts_with('"').not())
+            .find(|number| i64::from_str(number).is_err());
+
+        if let Some(number) = number {
+            return Err(ParseError::new(
+                Day12::DAY,
+                line,
+                number,
+                format!("expected an integer, found {number}"),
+            )
+            .on_line(idx + 1));
+        }
+    }
+
+    Ok(())
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    const DAY: u8 = 12;
+
+    type Input<'a> = Value;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input.trim())
+    }
+
+    fn part_1(input: &Self::Input<'_>) -> Self::Answer1 {
+        sum_all(input)
+    }
+
+    fn part_2(input: &Self::Input<'_>) -> Self::Answer2 {
+        sum_non_red(input)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::util::Input;
 
     #[test]
     fn day_12_part_01_sample() {
-        assert_eq!(6, solve_1(r#"[1,2,3]"#));
-        assert_eq!(6, solve_1(r#"{"a":2,"b":4}"#));
-        assert_eq!(3, solve_1(r#"[[[3]]]"#));
-        assert_eq!(3, solve_1(r#"{"a":{"b":4},"c":-1}"#));
-        assert_eq!(0, solve_1(r#"{"a":[-1,1]}"#));
-        assert_eq!(0, solve_1(r#"[-1,{"a":1}]"#));
-        assert_eq!(0, solve_1(r#"[]"#));
-        assert_eq!(0, solve_1(r#"{}"#));
+        assert_eq!(Ok(6), solve_1(r#"[1,2,3]"#));
+        assert_eq!(Ok(6), solve_1(r#"{"a":2,"b":4}"#));
+        assert_eq!(Ok(3), solve_1(r#"[[[3]]]"#));
+        assert_eq!(Ok(3), solve_1(r#"{"a":{"b":4},"c":-1}"#));
+        assert_eq!(Ok(0), solve_1(r#"{"a":[-1,1]}"#));
+        assert_eq!(Ok(0), solve_1(r#"[-1,{"a":1}]"#));
+        assert_eq!(Ok(0), solve_1(r#"[]"#));
+        assert_eq!(Ok(0), solve_1(r#"{}"#));
     }
 
     #[test]
     fn day_12_part_01_solution() {
-        let input = include_str!("../../inputs/day_12.txt").trim();
+        let input = Input::load(12).unwrap().text();
 
-        assert_eq!(119_433, solve_1(input));
+        assert_eq!(Ok(119_433), solve_1(input));
     }
 
     #[test]
     fn day_12_part_02_sample() {
-        assert_eq!(6, solve_2(r#"[1,2,3]"#));
-        assert_eq!(4, solve_2(r#"[1,{"c":"red","b":2},3]"#));
-        assert_eq!(0, solve_2(r#"{"d":"red","e":[1,2,3,4],"f":5}"#));
-        assert_eq!(6, solve_2(r#"[1,"red",5]"#));
+        assert_eq!(Ok(6), solve_2(r#"[1,2,3]"#));
+        assert_eq!(Ok(4), solve_2(r#"[1,{"c":"red","b":2},3]"#));
+        assert_eq!(Ok(0), solve_2(r#"{"d":"red","e":[1,2,3,4],"f":5}"#));
+        assert_eq!(Ok(6), solve_2(r#"[1,"red",5]"#));
     }
 
     #[test]
     fn day_12_part_02_solution() {
-        let input = include_str!("../../inputs/day_12.txt").trim();
+        let input = Input::load(12).unwrap().text();
+
+        assert_eq!(Ok(68_466), solve_2(input));
+    }
+
+    #[test]
+    fn day_12_parse_error() {
+        let error = solve_1(r#"{"a":[1,2}"#).unwrap_err();
+
+        assert_eq!((1, 10), (error.line, error.column));
+    }
+
+    #[test]
+    fn day_12_not_an_integer() {
+        let error = solve_1(r#"[1.5]"#).unwrap_err();
+        assert_eq!((1, 2), (error.line, error.column));
+        assert_eq!("expected an integer, found 1.5", error.reason);
 
-        assert_eq!(68_466, solve_2(input));
+        let error = solve_2("{\n  \"a\": \"1.5 \\\" 2e3\",\n  \"b\": [-1, 2e3]\n}").unwrap_err();
+        assert_eq!((3, 13), (error.line, error.column));
+        assert!(solve_1("[99999999999999999999]").is_err());
+        assert_eq!(Ok(-1), solve_1("[-1, 0]"));
     }
 }
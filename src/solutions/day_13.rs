@@ -1,18 +1,21 @@
-use itertools::Itertools;
-use std::str::FromStr;
+use std::ops::Not;
 
+use itertools::Itertools;
 use regex::Regex;
 use rustc_hash::{FxHashMap, FxHashSet};
 
-pub fn solve_1(relations: &[&str]) -> i16 {
-    optimal_happiness(Seating::new(relations))
+use crate::error::{parse_lines, parse_number, ParseError};
+use crate::solutions::Solution;
+
+pub fn solve_1(relations: &[&str]) -> Result<i16, ParseError> {
+    Ok(optimal_happiness(&Seating::new(relations)?))
 }
 
-pub fn solve_2(relations: &[&str]) -> i16 {
-    optimal_happiness(Seating::new(relations).add_me())
+pub fn solve_2(relations: &[&str]) -> Result<i16, ParseError> {
+    Ok(optimal_happiness(&Seating::new(relations)?.add_me()))
 }
 
-fn optimal_happiness(seating: Seating) -> i16 {
+fn optimal_happiness(seating: &Seating) -> i16 {
     seating
         .people
         .iter()
@@ -37,37 +40,70 @@ fn optimal_happiness(seating: Seating) -> i16 {
 }
 
 #[derive(Debug)]
-struct Seating<'a> {
+pub struct Seating<'a> {
     people: FxHashSet<&'a str>,
     relations: FxHashMap<(&'a str, &'a str), i16>,
 }
 
 impl<'a> Seating<'a> {
-    fn new(relations: &[&'a str]) -> Self {
-        let re = Regex::new(r"^(?<name_a>\w+) would (?<score>(gain|lose) \d+) happiness units by sitting next to (?<name_b>\w+)\.$").unwrap();
-
-        let relations: FxHashMap<(&'a str, &'a str), i16> = relations
+    /// Parses the relations, requiring one for every person towards every other person.
+    fn new(relations: &[&'a str]) -> Result<Self, ParseError> {
+        let missing = |reason: String| {
+            let last = relations.last().copied().unwrap_or_default();
+            ParseError::new(Day13::DAY, last, &last[last.len()..], reason)
+                .on_line(relations.len().max(1))
+        };
+        if relations.is_empty() {
+            return Err(missing("expected happiness relations".to_string()));
+        }
+
+        let re = Regex::new(r"^(?<name_a>\w+) would (?<sign>gain|lose) (?<score>\d+) happiness units by sitting next to (?<name_b>\w+)\.$").unwrap();
+
+        let relations: FxHashMap<(&'a str, &'a str), i16> = parse_lines(relations, |r| {
+            let caps = re.captures(r).ok_or_else(|| {
+                ParseError::new(
+                    Day13::DAY,
+                    r,
+                    r,
+                    "expected 'NAME would <gain|lose> N happiness units by sitting next to NAME.'",
+                )
+            })?;
+
+            let name_a = caps.name("name_a").unwrap().as_str();
+            let name_b = caps.name("name_b").unwrap().as_str();
+            if name_a == name_b {
+                return Err(ParseError::new(
+                    Day13::DAY,
+                    r,
+                    name_b,
+                    format!("{name_a} cannot sit next to themselves"),
+                ));
+            }
+            let score: i16 = parse_number(Day13::DAY, r, caps.name("score").unwrap().as_str())?;
+            let score = match &caps["sign"] {
+                "gain" => score,
+                _ => -score,
+            };
+
+            Ok(((name_a, name_b), score))
+        })?
+        .into_iter()
+        .collect();
+        let people: FxHashSet<&'a str> = relations.keys().flat_map(|&(a, b)| [a, b]).collect();
+
+        if let Some((a, b)) = people
             .iter()
-            .map(|r| {
-                let caps = re.captures(r).unwrap();
-
-                let name_a = caps.name("name_a").unwrap().as_str();
-                let name_b = caps.name("name_b").unwrap().as_str();
-                let score = caps.name("score").unwrap().as_str();
-                let score = if score.starts_with("gain") {
-                    i16::from_str(score.strip_prefix("gain ").unwrap()).unwrap()
-                } else if score.starts_with("lose") {
-                    -i16::from_str(score.strip_prefix("lose ").unwrap()).unwrap()
-                } else {
-                    unreachable!()
-                };
-
-                ((name_a, name_b), score)
-            })
-            .collect();
-        let people: FxHashSet<&'a str> = relations.keys().map(|(p, _)| *p).collect();
-
-        Self { people, relations }
+            .sorted()
+            .tuple_combinations()
+            .flat_map(|(&a, &b)| [(a, b), (b, a)])
+            .find(|pair| relations.contains_key(pair).not())
+        {
+            return Err(missing(format!(
+                "missing how {a} feels about sitting next to {b}"
+            )));
+        }
+
+        Ok(Self { people, relations })
     }
 
     fn add_me(&self) -> Self {
@@ -84,11 +120,32 @@ impl<'a> Seating<'a> {
     }
 }
 
+pub struct Day13;
+
+impl Solution for Day13 {
+    const DAY: u8 = 13;
+
+    type Input<'a> = Seating<'a>;
+    type Answer1 = i16;
+    type Answer2 = i16;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        Seating::new(&input.lines().collect_vec())
+    }
+
+    fn part_1(input: &Self::Input<'_>) -> Self::Answer1 {
+        optimal_happiness(input)
+    }
+
+    fn part_2(input: &Self::Input<'_>) -> Self::Answer2 {
+        optimal_happiness(&input.add_me())
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use itertools::Itertools;
-
     use super::*;
+    use crate::util::Input;
 
     #[test]
     fn day_13_part_01_sample() {
@@ -107,16 +164,14 @@ mod tests {
             "David would gain 41 happiness units by sitting next to Carol.",
         ];
 
-        assert_eq!(330, solve_1(&sample));
+        assert_eq!(Ok(330), solve_1(&sample));
     }
 
     #[test]
     fn day_13_part_01_solution() {
-        let input = include_str!("../../inputs/day_13.txt")
-            .lines()
-            .collect_vec();
+        let input = Input::load(13).unwrap().lines();
 
-        assert_eq!(664, solve_1(&input));
+        assert_eq!(Ok(664), solve_1(&input));
     }
 
     #[test]
@@ -126,10 +181,32 @@ mod tests {
 
     #[test]
     fn day_13_part_02_solution() {
-        let input = include_str!("../../inputs/day_13.txt")
-            .lines()
-            .collect_vec();
+        let input = Input::load(13).unwrap().lines();
+
+        assert_eq!(Ok(640), solve_2(&input));
+    }
+
+    #[test]
+    fn day_13_parse_error() {
+        let error =
+            solve_1(&["Alice would win 54 happiness units by sitting next to Bob."]).unwrap_err();
 
-        assert_eq!(640, solve_2(&input));
+        assert_eq!((1, 1), (error.line, error.column));
+
+        let error = solve_1(&[
+            "Alice would gain 54 happiness units by sitting next to Bob.",
+            "Bob would lose 7 happiness units by sitting next to Carol.",
+        ])
+        .unwrap_err();
+        assert_eq!((2, 59), (error.line, error.column));
+        assert_eq!(
+            "missing how Bob feels about sitting next to Alice",
+            error.reason
+        );
+
+        let error = solve_1(&["Alice would gain 54 happiness units by sitting next to Alice."])
+            .unwrap_err();
+        assert_eq!((1, 56), (error.line, error.column));
+        assert!(solve_1(&[]).is_err());
     }
 }
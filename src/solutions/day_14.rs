@@ -1,27 +1,36 @@
-use std::str::FromStr;
-
 use itertools::Itertools;
 use regex::Regex;
 
-pub fn solve_1(reindeer: &[&str], time: u32) -> u32 {
-    Race::new(reindeer).result(time).max_distance
+use crate::error::{parse_lines, parse_number, ParseError};
+use crate::solutions::Solution;
+
+pub fn solve_1(reindeer: &[&str], time: u32) -> Result<u32, ParseError> {
+    Ok(Race::new(reindeer)?.result(time).max_distance)
 }
 
-pub fn solve_2(reindeer: &[&str], time: u32) -> u32 {
-    Race::new(reindeer).result(time).max_points
+pub fn solve_2(reindeer: &[&str], time: u32) -> Result<u32, ParseError> {
+    Ok(Race::new(reindeer)?.result(time).max_points)
 }
 
 #[derive(Debug)]
-struct Race {
+pub struct Race {
     reindeer: Vec<Reindeer>,
 }
 
 impl Race {
-    fn new(reindeer: &[&str]) -> Self {
+    fn new(reindeer: &[&str]) -> Result<Self, ParseError> {
         let re = Regex::new(r"^(:?(\w+)) can fly (?<speed>\d+) km/s for (?<fly_time>\d+) seconds, but then must rest for (?<rest_time>\d+) seconds\.$").unwrap();
-        let reindeer = reindeer.iter().map(|r| Reindeer::new(r, &re)).collect_vec();
+        if reindeer.is_empty() {
+            return Err(ParseError::new(
+                Day14::DAY,
+                "",
+                "",
+                "expected at least one reindeer",
+            ));
+        }
+        let reindeer = parse_lines(reindeer, |r| Reindeer::new(r, &re))?;
 
-        Self { reindeer }
+        Ok(Self { reindeer })
     }
 
     fn result(&self, time: u32) -> RaceResult {
@@ -62,18 +71,32 @@ struct Reindeer {
 }
 
 impl Reindeer {
-    fn new(reindeer: &str, re: &Regex) -> Reindeer {
-        let caps = re.captures(reindeer).unwrap();
-
-        let speed = u32::from_str(caps.name("speed").unwrap().as_str()).unwrap();
-        let fly_time = u32::from_str(caps.name("fly_time").unwrap().as_str()).unwrap();
-        let rest_time = u32::from_str(caps.name("rest_time").unwrap().as_str()).unwrap();
+    fn new(reindeer: &str, re: &Regex) -> Result<Reindeer, ParseError> {
+        let caps = re.captures(reindeer).ok_or_else(|| {
+            ParseError::new(
+                Day14::DAY,
+                reindeer,
+                reindeer,
+                "expected 'NAME can fly N km/s for N seconds, but then must rest for N seconds.'",
+            )
+        })?;
+        let number = |name| parse_number(Day14::DAY, reindeer, caps.name(name).unwrap().as_str());
+
+        let (fly_time, rest_time) = (number("fly_time")?, number("rest_time")?);
+        if fly_time + rest_time == 0 {
+            return Err(ParseError::new(
+                Day14::DAY,
+                reindeer,
+                caps.name("fly_time").unwrap().as_str(),
+                "expected the reindeer to fly or rest for at least 1 second",
+            ));
+        }
 
-        Self {
-            speed,
+        Ok(Self {
+            speed: number("speed")?,
             fly_time,
             rest_time,
-        }
+        })
     }
 }
 
@@ -83,11 +106,32 @@ struct RaceResult {
     max_points: u32,
 }
 
+pub struct Day14;
+
+impl Solution for Day14 {
+    const DAY: u8 = 14;
+
+    type Input<'a> = Race;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        Race::new(&input.lines().collect_vec())
+    }
+
+    fn part_1(input: &Self::Input<'_>) -> Self::Answer1 {
+        input.result(2_503).max_distance
+    }
+
+    fn part_2(input: &Self::Input<'_>) -> Self::Answer2 {
+        input.result(2_503).max_points
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use itertools::Itertools;
-
     use super::*;
+    use crate::util::Input;
 
     #[test]
     fn day_14_part_01_sample() {
@@ -96,16 +140,14 @@ mod tests {
             "Dancer can fly 16 km/s for 11 seconds, but then must rest for 162 seconds.",
         ];
 
-        assert_eq!(1_120, solve_1(&sample, 1_000));
+        assert_eq!(Ok(1_120), solve_1(&sample, 1_000));
     }
 
     #[test]
     fn day_14_part_01_solution() {
-        let input = include_str!("../../inputs/day_14.txt")
-            .lines()
-            .collect_vec();
+        let input = Input::load(14).unwrap().lines();
 
-        assert_eq!(2_660, solve_1(&input, 2_503));
+        assert_eq!(Ok(2_660), solve_1(&input, 2_503));
     }
 
     #[test]
@@ -115,15 +157,32 @@ mod tests {
             "Dancer can fly 16 km/s for 11 seconds, but then must rest for 162 seconds.",
         ];
 
-        assert_eq!(689, solve_2(&sample, 1_000));
+        assert_eq!(Ok(689), solve_2(&sample, 1_000));
     }
 
     #[test]
     fn day_14_part_02_solution() {
-        let input = include_str!("../../inputs/day_14.txt")
-            .lines()
-            .collect_vec();
+        let input = Input::load(14).unwrap().lines();
 
-        assert_eq!(1_256, solve_2(&input, 2_503));
+        assert_eq!(Ok(1_256), solve_2(&input, 2_503));
+    }
+
+    #[test]
+    fn day_14_parse_error() {
+        let error = solve_1(
+            &["Comet can fly 14 km/s for ten seconds, but then must rest for 127 seconds."],
+            1_000,
+        )
+        .unwrap_err();
+
+        assert_eq!((1, 1), (error.line, error.column));
+
+        let error = solve_1(
+            &["Comet can fly 14 km/s for 0 seconds, but then must rest for 0 seconds."],
+            1_000,
+        )
+        .unwrap_err();
+        assert_eq!((1, 27), (error.line, error.column));
+        assert!(solve_2(&[], 1_000).is_err());
     }
 }
@@ -1,63 +1,65 @@
 use itertools::Itertools;
-use std::str::FromStr;
-
 use regex::Regex;
 
-pub fn solve_1(ingredients: &[&str]) -> i64 {
-    Recipe::new(ingredients).perfect(false)
+use crate::error::{parse_lines, parse_number, ParseError};
+use crate::solutions::Solution;
+
+pub fn solve_1(ingredients: &[&str]) -> Result<i64, ParseError> {
+    Ok(Recipe::new(ingredients)?.perfect(false))
 }
 
-pub fn solve_2(ingredients: &[&str]) -> i64 {
-    Recipe::new(ingredients).perfect(true)
+pub fn solve_2(ingredients: &[&str]) -> Result<i64, ParseError> {
+    Ok(Recipe::new(ingredients)?.perfect(true))
 }
 
+const TEASPOONS: i64 = 100;
+
 #[derive(Debug)]
-struct Recipe {
+pub struct Recipe {
     ingredients: Vec<Ingredient>,
 }
 
 impl Recipe {
-    fn new(ingredients: &[&str]) -> Self {
+    fn new(ingredients: &[&str]) -> Result<Self, ParseError> {
         let re =
             Regex::new(r"^(:?\w+): capacity (?<capacity>-?\d+), durability (?<durability>-?\d+), flavor (?<flavor>-?\d+), texture (?<texture>-?\d+), calories (?<calories>-?\d+)$")
                 .unwrap();
-        let mut ingredients = ingredients
-            .iter()
-            .map(|i| Ingredient::new(i, &re))
-            .collect_vec();
-
-        // for the sample code case, we add dummy ingredients to keep the code short
-        if ingredients.len() == 2 {
-            let bad = Ingredient {
-                capacity: -999,
-                durability: -999,
-                flavor: -999,
-                texture: -999,
-                calories: -999,
-            };
-            ingredients.push(bad);
-            ingredients.push(bad);
+        if ingredients.is_empty() {
+            return Err(ParseError::new(
+                Day15::DAY,
+                "",
+                "",
+                "expected at least one ingredient",
+            ));
         }
+        let ingredients = parse_lines(ingredients, |i| Ingredient::new(i, &re))?;
 
-        Self { ingredients }
+        Ok(Self { ingredients })
     }
 
     fn perfect(&self, calories: bool) -> i64 {
-        let mut perfect = 0;
-
-        for a in 0..=100 {
-            for b in 0..=100 - a {
-                for c in 0..=100 - a - b {
-                    let d = 100 - a - b - c;
-                    perfect = perfect.max(Self::score(self, &vec![a, b, c, d], calories));
-                }
-            }
+        let mut proportions = vec![0; self.ingredients.len()];
+
+        self.best(&mut proportions, 0, TEASPOONS, calories)
+    }
+
+    /// Best score dividing the teaspoons `left` over the ingredients from `idx` on.
+    fn best(&self, proportions: &mut [i64], idx: usize, left: i64, calories: bool) -> i64 {
+        if idx == proportions.len() - 1 {
+            proportions[idx] = left;
+            return self.score(proportions, calories);
         }
 
-        perfect
+        (0..=left)
+            .map(|teaspoons| {
+                proportions[idx] = teaspoons;
+                self.best(proportions, idx + 1, left - teaspoons, calories)
+            })
+            .max()
+            .unwrap_or(0)
     }
 
-    fn score(&self, proportions: &Vec<i64>, calories: bool) -> i64 {
+    fn score(&self, proportions: &[i64], calories: bool) -> i64 {
         let total_capacity = (0..proportions.len())
             .map(|idx| self.ingredients[idx].capacity * proportions[idx])
             .sum::<i64>()
@@ -97,30 +99,53 @@ struct Ingredient {
 }
 
 impl Ingredient {
-    fn new(ingredient: &str, re: &Regex) -> Self {
-        let caps = re.captures(ingredient).unwrap();
-
-        let capacity = i64::from_str(caps.name("capacity").unwrap().as_str()).unwrap();
-        let durability = i64::from_str(caps.name("durability").unwrap().as_str()).unwrap();
-        let flavor = i64::from_str(caps.name("flavor").unwrap().as_str()).unwrap();
-        let texture = i64::from_str(caps.name("texture").unwrap().as_str()).unwrap();
-        let calories = i64::from_str(caps.name("calories").unwrap().as_str()).unwrap();
-
-        Self {
-            capacity,
-            durability,
-            flavor,
-            texture,
-            calories,
-        }
+    fn new(ingredient: &str, re: &Regex) -> Result<Self, ParseError> {
+        let caps = re.captures(ingredient).ok_or_else(|| {
+            ParseError::new(
+                Day15::DAY,
+                ingredient,
+                ingredient,
+                "expected 'NAME: capacity N, durability N, flavor N, texture N, calories N'",
+            )
+        })?;
+        let number = |name| parse_number(Day15::DAY, ingredient, caps.name(name).unwrap().as_str());
+
+        Ok(Self {
+            capacity: number("capacity")?,
+            durability: number("durability")?,
+            flavor: number("flavor")?,
+            texture: number("texture")?,
+            calories: number("calories")?,
+        })
+    }
+}
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    const DAY: u8 = 15;
+
+    type Input<'a> = Recipe;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        Recipe::new(&input.lines().collect_vec())
+    }
+
+    fn part_1(input: &Self::Input<'_>) -> Self::Answer1 {
+        input.perfect(false)
+    }
+
+    fn part_2(input: &Self::Input<'_>) -> Self::Answer2 {
+        input.perfect(true)
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use itertools::Itertools;
-
     use super::*;
+    use crate::util::Input;
 
     #[test]
     fn day_15_part_01_sample() {
@@ -129,16 +154,14 @@ mod tests {
             "Cinnamon: capacity 2, durability 3, flavor -2, texture -1, calories 3",
         ];
 
-        assert_eq!(62_842_880, solve_1(&sample));
+        assert_eq!(Ok(62_842_880), solve_1(&sample));
     }
 
     #[test]
     fn day_15_part_01_solution() {
-        let input = include_str!("../../inputs/day_15.txt")
-            .lines()
-            .collect_vec();
+        let input = Input::load(15).unwrap().lines();
 
-        assert_eq!(13_882_464, solve_1(&input));
+        assert_eq!(Ok(13_882_464), solve_1(&input));
     }
 
     #[test]
@@ -148,15 +171,37 @@ mod tests {
             "Cinnamon: capacity 2, durability 3, flavor -2, texture -1, calories 3",
         ];
 
-        assert_eq!(57_600_000, solve_2(&sample));
+        assert_eq!(Ok(57_600_000), solve_2(&sample));
     }
 
     #[test]
     fn day_15_part_02_solution() {
-        let input = include_str!("../../inputs/day_15.txt")
-            .lines()
-            .collect_vec();
+        let input = Input::load(15).unwrap().lines();
+
+        assert_eq!(Ok(11_171_160), solve_2(&input));
+    }
+
+    #[test]
+    fn day_15_parse_error() {
+        let error = solve_1(&["Butterscotch: capacity -1, durability -2, flavor 6, texture 3"])
+            .unwrap_err();
+
+        assert_eq!((1, 1), (error.line, error.column));
+        assert!(solve_1(&[]).is_err());
+    }
+
+    #[test]
+    fn day_15_any_number_of_ingredients() {
+        let sample = [
+            "Butterscotch: capacity -1, durability -2, flavor 6, texture 3, calories 8",
+            "Cinnamon: capacity 2, durability 3, flavor -2, texture -1, calories 3",
+            "Sugar: capacity 0, durability 0, flavor 0, texture 0, calories 0",
+        ];
 
-        assert_eq!(11_171_160, solve_2(&input));
+        assert_eq!(Ok(62_842_880), solve_1(&sample));
+        assert_eq!(
+            Ok(100_000_000),
+            solve_1(&["Cream: capacity 1, durability 1, flavor 1, texture 1, calories 5"])
+        );
     }
 }
@@ -1,29 +1,64 @@
 use std::ops::Not;
-use std::str::FromStr;
 
 use itertools::Itertools;
 use lazy_static::lazy_static;
 use regex::Regex;
 use rustc_hash::FxHashMap;
 
-pub fn solve_1(sues: &[&str]) -> u16 {
+use crate::error::{parse_lines, parse_number, ParseError};
+use crate::solutions::Solution;
+
+pub fn solve_1(sues: &[&str]) -> Result<u16, ParseError> {
+    exact_match(&parse(sues)?)
+}
+
+pub fn solve_2(sues: &[&str]) -> Result<u16, ParseError> {
+    range_match(&parse(sues)?)
+}
+
+/// The parsed Sues, along with their lines to point at in errors.
+pub struct Sues<'a> {
+    lines: Vec<&'a str>,
+    sues: Vec<Sue<'a>>,
+}
+
+fn parse<'a>(lines: &[&'a str]) -> Result<Sues<'a>, ParseError> {
+    Ok(Sues {
+        lines: lines.to_vec(),
+        sues: parse_lines(lines, Sue::new)?,
+    })
+}
+
+fn exact_match(sues: &Sues) -> Result<u16, ParseError> {
     let tape = read_tape();
 
-    sues.iter()
-        .map(|s| Sue::new(s))
-        .find(|s| s.valid_exact(&tape))
-        .unwrap()
-        .number
+    find(
+        sues,
+        |s| s.valid_exact(&tape),
+        "no Sue matches the tape exactly",
+    )
 }
 
-pub fn solve_2(sues: &[&str]) -> u16 {
+fn range_match(sues: &Sues) -> Result<u16, ParseError> {
     let tape = read_tape();
 
-    sues.iter()
-        .map(|s| Sue::new(s))
-        .find(|s| s.valid_ranges(&tape))
-        .unwrap()
-        .number
+    find(
+        sues,
+        |s| s.valid_ranges(&tape),
+        "no Sue matches the ranges of the tape",
+    )
+}
+
+fn find(sues: &Sues, valid: impl Fn(&Sue) -> bool, unmatched: &str) -> Result<u16, ParseError> {
+    if let Some(sue) = sues.sues.iter().find(|&s| valid(s)) {
+        return Ok(sue.number);
+    }
+
+    let last = sues.lines.last().copied().unwrap_or_default();
+    Err(
+        ParseError::new(Day16::DAY, last, &last[last.len()..], unmatched)
+            .on_line(sues.lines.len().max(1)),
+    )
 }
 
 fn read_tape<'a>() -> FxHashMap<&'a str, u16> {
@@ -48,28 +83,35 @@ lazy_static! {
 }
 
 #[derive(Debug)]
-struct Sue<'a> {
+pub struct Sue<'a> {
     number: u16,
     properties: FxHashMap<&'a str, u16>,
 }
 
 impl<'a> Sue<'a> {
-    fn new(sue: &'a str) -> Self {
-        let caps = RE.captures(sue).unwrap();
+    fn new(sue: &'a str) -> Result<Self, ParseError> {
+        let caps = RE
+            .captures(sue)
+            .ok_or_else(|| ParseError::new(Day16::DAY, sue, sue, "expected 'Sue N: PROPERTIES'"))?;
 
-        let number = u16::from_str(caps.name("number").unwrap().as_str()).unwrap();
+        let number = parse_number(Day16::DAY, sue, caps.name("number").unwrap().as_str())?;
         let properties = caps
             .name("properties")
             .unwrap()
             .as_str()
             .split(", ")
-            .map(|p| {
-                let split = p.split(": ").collect_vec();
-                (split[0], u16::from_str(split[1]).unwrap())
+            .map(|p| match p.split(": ").collect_vec()[..] {
+                [name, value] => Ok((name, parse_number(Day16::DAY, sue, value)?)),
+                _ => Err(ParseError::new(
+                    Day16::DAY,
+                    sue,
+                    p,
+                    "expected property 'NAME: N'",
+                )),
             })
-            .collect();
+            .collect::<Result<_, _>>()?;
 
-        Self { number, properties }
+        Ok(Self { number, properties })
     }
 
     fn valid_exact(&self, tape: &FxHashMap<&str, u16>) -> bool {
@@ -90,11 +132,32 @@ impl<'a> Sue<'a> {
     }
 }
 
+pub struct Day16;
+
+impl Solution for Day16 {
+    const DAY: u8 = 16;
+
+    type Input<'a> = Sues<'a>;
+    type Answer1 = Result<u16, ParseError>;
+    type Answer2 = Result<u16, ParseError>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(&input.lines().collect_vec())
+    }
+
+    fn part_1(input: &Self::Input<'_>) -> Self::Answer1 {
+        exact_match(input)
+    }
+
+    fn part_2(input: &Self::Input<'_>) -> Self::Answer2 {
+        range_match(input)
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use itertools::Itertools;
-
     use super::*;
+    use crate::util::Input;
 
     #[test]
     fn day_16_part_01_sample() {
@@ -103,11 +166,9 @@ mod tests {
 
     #[test]
     fn day_16_part_01_solution() {
-        let input = include_str!("../../inputs/day_16.txt")
-            .lines()
-            .collect_vec();
+        let input = Input::load(16).unwrap().lines();
 
-        assert_eq!(40, solve_1(&input));
+        assert_eq!(Ok(40), solve_1(&input));
     }
 
     #[test]
@@ -117,10 +178,26 @@ mod tests {
 
     #[test]
     fn day_16_part_02_solution() {
-        let input = include_str!("../../inputs/day_16.txt")
-            .lines()
-            .collect_vec();
+        let input = Input::load(16).unwrap().lines();
+
+        assert_eq!(Ok(241), solve_2(&input));
+    }
+
+    #[test]
+    fn day_16_parse_error() {
+        let error = solve_1(&["Sue 1: cars: 9, akitas 3, goldfish: 0"]).unwrap_err();
+
+        assert_eq!((1, 17), (error.line, error.column));
+
+        let error = solve_1(&["Sue 1: cars: 9", "Sue 2: cats: 8"]).unwrap_err();
+        assert_eq!((2, 15), (error.line, error.column));
+        assert_eq!("no Sue matches the tape exactly", error.reason);
+
+        assert_eq!(Ok(2), solve_2(&["Sue 1: cars: 9", "Sue 2: cats: 8"]));
 
-        assert_eq!(241, solve_2(&input));
+        let error = solve_2(&["Sue 1: cars: 2, cats: 7"]).unwrap_err();
+        assert_eq!("no Sue matches the ranges of the tape", error.reason);
+        assert_eq!(Ok(1), solve_1(&["Sue 1: cars: 2, cats: 7"]));
+        assert!(solve_1(&[]).is_err());
     }
 }
@@ -1,38 +1,56 @@
-use std::str::FromStr;
-
 use itertools::Itertools;
 
-pub fn solve_1(containers: &[&str], liters: u16) -> usize {
-    let containers = parse_containers(containers);
-    let acc = generate_combinations(&containers);
+use crate::error::{parse_lines, parse_number, ParseError};
+use crate::solutions::Solution;
+
+pub fn solve_1(containers: &[&str], liters: u16) -> Result<usize, ParseError> {
+    Ok(combinations(&parse_containers(containers)?, liters))
+}
 
-    fitting(liters, &containers, &acc).count()
+pub fn solve_2(containers: &[&str], liters: u16) -> Result<usize, ParseError> {
+    Ok(smallest_combinations(
+        &parse_containers(containers)?,
+        liters,
+    ))
 }
 
-pub fn solve_2(containers: &[&str], liters: u16) -> usize {
-    let containers = parse_containers(containers);
-    let combinations = generate_combinations(&containers);
+fn combinations(containers: &[u16], liters: u16) -> usize {
+    let acc = generate_combinations(containers);
 
-    let min_containers = fitting(liters, &containers, &combinations)
+    fitting(liters, containers, &acc).count()
+}
+
+fn smallest_combinations(containers: &[u16], liters: u16) -> usize {
+    let combinations = generate_combinations(containers);
+
+    let Some(min_containers) = fitting(liters, containers, &combinations)
         .map(|cs| cs.iter().filter(|&&b| b).count())
         .min()
-        .unwrap();
+    else {
+        return 0;
+    };
     let smallest_combinations = combinations
         .into_iter()
         .filter(|cs| cs.iter().filter(|&&b| b).count() == min_containers)
         .collect_vec();
 
-    fitting(liters, &containers, &smallest_combinations).count()
+    fitting(liters, containers, &smallest_combinations).count()
 }
 
-fn parse_containers(containers: &[&str]) -> Vec<u16> {
-    containers
-        .iter()
-        .map(|c| u16::from_str(c).unwrap())
-        .collect_vec()
+fn parse_containers(containers: &[&str]) -> Result<Vec<u16>, ParseError> {
+    if containers.is_empty() {
+        return Err(ParseError::new(
+            Day17::DAY,
+            "",
+            "",
+            "expected at least one container",
+        ));
+    }
+
+    parse_lines(containers, |c| parse_number(Day17::DAY, c, c))
 }
 
-fn generate_combinations(containers: &Vec<u16>) -> Vec<Vec<bool>> {
+fn generate_combinations(containers: &[u16]) -> Vec<Vec<bool>> {
     let mut current = Vec::with_capacity(containers.len());
     let mut acc = Vec::new();
     generate_combinations_rec(containers.len(), &mut current, &mut acc);
@@ -68,41 +86,72 @@ fn fitting<'a>(
     })
 }
 
+pub struct Day17;
+
+impl Solution for Day17 {
+    const DAY: u8 = 17;
+
+    type Input<'a> = Vec<u16>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_containers(&input.lines().collect_vec())
+    }
+
+    fn part_1(input: &Self::Input<'_>) -> Self::Answer1 {
+        combinations(input, 150)
+    }
+
+    fn part_2(input: &Self::Input<'_>) -> Self::Answer2 {
+        smallest_combinations(input, 150)
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use itertools::Itertools;
-
     use super::*;
+    use crate::util::Input;
 
     #[test]
     fn day_17_part_01_sample() {
         let sample = vec!["20", "15", "10", "5", "5"];
 
-        assert_eq!(4, solve_1(&sample, 25));
+        assert_eq!(Ok(4), solve_1(&sample, 25));
     }
 
     #[test]
     fn day_17_part_01_solution() {
-        let input = include_str!("../../inputs/day_17.txt")
-            .lines()
-            .collect_vec();
+        let input = Input::load(17).unwrap().lines();
 
-        assert_eq!(1_638, solve_1(&input, 150));
+        assert_eq!(Ok(1_638), solve_1(&input, 150));
     }
 
     #[test]
     fn day_17_part_02_sample() {
         let sample = vec!["20", "15", "10", "5", "5"];
 
-        assert_eq!(3, solve_2(&sample, 25));
+        assert_eq!(Ok(3), solve_2(&sample, 25));
     }
 
     #[test]
     fn day_17_part_02_solution() {
-        let input = include_str!("../../inputs/day_17.txt")
-            .lines()
-            .collect_vec();
+        let input = Input::load(17).unwrap().lines();
 
-        assert_eq!(17, solve_2(&input, 150));
+        assert_eq!(Ok(17), solve_2(&input, 150));
+    }
+
+    #[test]
+    fn day_17_parse_error() {
+        let error = solve_1(&["20", "15", "1O"], 25).unwrap_err();
+
+        assert_eq!((3, 1), (error.line, error.column));
+        assert!(solve_2(&[], 25).is_err());
+    }
+
+    #[test]
+    fn day_17_nothing_fits() {
+        assert_eq!(Ok(0), solve_1(&["20", "15"], 25));
+        assert_eq!(Ok(0), solve_2(&["20", "15"], 25));
     }
 }
@@ -1,6 +1,9 @@
 use itertools::Itertools;
 use rustc_hash::FxHashMap;
 
+use crate::error::ParseError;
+use crate::solutions::Solution;
+
 const NEIGHBOURS: [Coordinate; 8] = [
     Coordinate { x: 0, y: -1 },
     Coordinate { x: 1, y: -1 },
@@ -12,16 +15,16 @@ const NEIGHBOURS: [Coordinate; 8] = [
     Coordinate { x: -1, y: -1 },
 ];
 
-pub fn solve_1(lights: &[&str], steps: u8) -> usize {
-    solve(lights, steps, false)
+pub fn solve_1(lights: &[&str], steps: u8) -> Result<usize, ParseError> {
+    Ok(animate(&Lights::new(lights)?, steps, false))
 }
 
-pub fn solve_2(lights: &[&str], steps: u8) -> usize {
-    solve(lights, steps, true)
+pub fn solve_2(lights: &[&str], steps: u8) -> Result<usize, ParseError> {
+    Ok(animate(&Lights::new(lights)?, steps, true))
 }
 
-fn solve(lights: &[&str], steps: u8, stuck: bool) -> usize {
-    let mut lights = Lights::new(lights);
+fn animate(lights: &Lights, steps: u8, stuck: bool) -> usize {
+    let mut lights = lights.clone();
 
     if stuck {
         lights.light_corners()
@@ -38,24 +41,43 @@ fn solve(lights: &[&str], steps: u8, stuck: bool) -> usize {
         .count()
 }
 
-#[derive(Debug)]
-struct Lights {
+#[derive(Debug, Clone)]
+pub struct Lights {
     grid: FxHashMap<Coordinate, State>,
     x_max: i8,
     y_max: i8,
 }
 
 impl Lights {
-    fn new(lights: &[&str]) -> Self {
-        let lights = lights.iter().map(|s| s.as_bytes()).collect_vec();
+    fn new(lights: &[&str]) -> Result<Self, ParseError> {
+        let first = lights.first().copied().unwrap_or_default();
+        let width = first.len();
+        if width == 0 {
+            return Err(ParseError::new(
+                Day18::DAY,
+                first,
+                first,
+                "expected at least one light",
+            ));
+        }
         let mut grid = FxHashMap::default();
 
-        for y in 0..lights.len() {
-            for x in 0..lights[0].len() {
-                let state = match lights[y][x] {
-                    b'#' => State::On,
-                    b'.' => State::Off,
-                    _ => unreachable!(),
+        for (y, row) in lights.iter().enumerate() {
+            let error =
+                |at, reason: &str| ParseError::new(Day18::DAY, row, at, reason).on_line(y + 1);
+
+            if row.len() != width || width > i8::MAX as usize || y > i8::MAX as usize {
+                return Err(error(
+                    row,
+                    "expected rows of equal length, at most 127 by 127 lights",
+                ));
+            }
+
+            for (x, light) in row.char_indices() {
+                let state = match light {
+                    '#' => State::On,
+                    '.' => State::Off,
+                    _ => return Err(error(&row[x..], "expected '#' or '.'")),
                 };
                 let coordinate = Coordinate {
                     x: x as i8,
@@ -66,10 +88,10 @@ impl Lights {
             }
         }
 
-        let x_max = lights[0].len() as i8;
+        let x_max = width as i8;
         let y_max = lights.len() as i8;
 
-        Self { grid, x_max, y_max }
+        Ok(Self { grid, x_max, y_max })
     }
 
     fn step(&self, stuck: bool) -> Self {
@@ -160,41 +182,69 @@ enum State {
     Off,
 }
 
+pub struct Day18;
+
+impl Solution for Day18 {
+    const DAY: u8 = 18;
+
+    type Input<'a> = Lights;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        Lights::new(&input.lines().collect_vec())
+    }
+
+    fn part_1(input: &Self::Input<'_>) -> Self::Answer1 {
+        animate(input, 100, false)
+    }
+
+    fn part_2(input: &Self::Input<'_>) -> Self::Answer2 {
+        animate(input, 100, true)
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use itertools::Itertools;
-
     use super::*;
+    use crate::util::Input;
 
     #[test]
     fn day_18_part_01_sample() {
         let sample = vec![".#.#.#", "...##.", "#....#", "..#...", "#.#..#", "####.."];
 
-        assert_eq!(4, solve_1(&sample, 4));
+        assert_eq!(Ok(4), solve_1(&sample, 4));
     }
 
     #[test]
     fn day_18_part_01_solution() {
-        let input = include_str!("../../inputs/day_18.txt")
-            .lines()
-            .collect_vec();
+        let input = Input::load(18).unwrap().lines();
 
-        assert_eq!(814, solve_1(&input, 100));
+        assert_eq!(Ok(814), solve_1(&input, 100));
     }
 
     #[test]
     fn day_18_part_02_sample() {
         let sample = vec!["##.#.#", "...##.", "#....#", "..#...", "#.#..#", "####.#"];
 
-        assert_eq!(17, solve_2(&sample, 5));
+        assert_eq!(Ok(17), solve_2(&sample, 5));
     }
 
     #[test]
     fn day_18_part_02_solution() {
-        let input = include_str!("../../inputs/day_18.txt")
-            .lines()
-            .collect_vec();
+        let input = Input::load(18).unwrap().lines();
+
+        assert_eq!(Ok(924), solve_2(&input, 100));
+    }
+
+    #[test]
+    fn day_18_parse_error() {
+        let error = solve_1(&[".#.", "..o", "###"], 1).unwrap_err();
+
+        assert_eq!((2, 3), (error.line, error.column));
 
-        assert_eq!(924, solve_2(&input, 100));
+        assert!(solve_2(&[], 1).is_err());
+        assert!(solve_2(&["", ""], 1).is_err());
+        assert_eq!(Ok(1), solve_2(&["."], 1));
     }
 }
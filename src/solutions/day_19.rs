@@ -1,14 +1,21 @@
 use itertools::Itertools;
 use rustc_hash::FxHashSet;
 
-pub fn solve_1(machine: &str) -> usize {
-    Machine::new(machine).generate_molecules().len()
+use crate::error::{parse_lines, ParseError};
+use crate::solutions::Solution;
+
+pub fn solve_1(machine: &str) -> Result<usize, ParseError> {
+    Ok(Machine::new(machine)?.generate_molecules().len())
 }
 
 // Answer based on https://www.reddit.com/r/adventofcode/comments/3xflz8/day_19_solutions/cy4h7ji/
 // Answer = #NumSymbols - #Rn - #Ar - 2 * #Y - 1
-pub fn solve_2(machine: &str) -> usize {
-    let molecule = Machine::new(machine).molecule;
+pub fn solve_2(machine: &str) -> Result<usize, ParseError> {
+    Ok(fewest_steps(&Machine::new(machine)?))
+}
+
+fn fewest_steps(machine: &Machine) -> usize {
+    let molecule = machine.molecule;
 
     let nr_symbols = molecule.chars().filter(|c| c.is_uppercase()).count();
     let nr_rn = molecule.match_indices("Rn").count();
@@ -19,28 +26,32 @@ pub fn solve_2(machine: &str) -> usize {
 }
 
 #[derive(Debug)]
-struct Machine<'a> {
+pub struct Machine<'a> {
     transformations: Vec<(&'a str, &'a str)>,
     molecule: &'a str,
 }
 
 impl<'a> Machine<'a> {
-    fn new(machine: &'a str) -> Self {
-        let split = machine.split("\n\n").collect_vec();
-
-        let transformations = split[0]
-            .split('\n')
-            .map(|s| {
-                let replacement = s.split(" => ").collect_vec();
-                (replacement[0], replacement[1])
+    fn new(machine: &'a str) -> Result<Self, ParseError> {
+        let Some((transformations, molecule)) = machine.split_once("\n\n") else {
+            return Err(ParseError::new(
+                Day19::DAY,
+                machine,
+                machine,
+                "expected replacements and the molecule separated by a blank line",
+            ));
+        };
+
+        let transformations = parse_lines(&transformations.lines().collect_vec(), |s| {
+            s.split_once(" => ").ok_or_else(|| {
+                ParseError::new(Day19::DAY, s, s, "expected replacement 'FROM => TO'")
             })
-            .collect();
-        let molecule = split[1];
+        })?;
 
-        Self {
+        Ok(Self {
             transformations,
             molecule,
-        }
+        })
     }
 
     fn generate_molecules(&self) -> FxHashSet<String> {
@@ -60,9 +71,32 @@ impl<'a> Machine<'a> {
     }
 }
 
+pub struct Day19;
+
+impl Solution for Day19 {
+    const DAY: u8 = 19;
+
+    type Input<'a> = Machine<'a>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        Machine::new(input.trim())
+    }
+
+    fn part_1(input: &Self::Input<'_>) -> Self::Answer1 {
+        input.generate_molecules().len()
+    }
+
+    fn part_2(input: &Self::Input<'_>) -> Self::Answer2 {
+        fewest_steps(input)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::util::Input;
 
     #[test]
     fn day_19_part_01_sample() {
@@ -72,7 +106,7 @@ mod tests {
             \n\
             HOH";
 
-        assert_eq!(4, solve_1(sample));
+        assert_eq!(Ok(4), solve_1(sample));
 
         let sample = "H => HO\n\
             H => OH\n\
@@ -80,14 +114,14 @@ mod tests {
             \n\
             HOHOHO";
 
-        assert_eq!(7, solve_1(sample));
+        assert_eq!(Ok(7), solve_1(sample));
     }
 
     #[test]
     fn day_19_part_01_solution() {
-        let input = include_str!("../../inputs/day_19.txt").trim();
+        let input = Input::load(19).unwrap().text();
 
-        assert_eq!(509, solve_1(input));
+        assert_eq!(Ok(509), solve_1(input));
     }
 
     #[test]
@@ -100,7 +134,7 @@ mod tests {
             \n\
             HOH";
 
-        assert_eq!(3 - 1, solve_2(sample));
+        assert_eq!(Ok(3 - 1), solve_2(sample));
 
         let sample = "e => H\n\
             e => O\n\
@@ -110,13 +144,20 @@ mod tests {
             \n\
             HOHOHO";
 
-        assert_eq!(6 - 1, solve_2(sample));
+        assert_eq!(Ok(6 - 1), solve_2(sample));
     }
 
     #[test]
     fn day_19_part_02_solution() {
-        let input = include_str!("../../inputs/day_19.txt").trim();
+        let input = Input::load(19).unwrap().text();
+
+        assert_eq!(Ok(195), solve_2(input));
+    }
+
+    #[test]
+    fn day_19_parse_error() {
+        let error = solve_1("H => HO\nH -> OH\n\nHOH").unwrap_err();
 
-        assert_eq!(195, solve_2(input));
+        assert_eq!((2, 1), (error.line, error.column));
     }
 }
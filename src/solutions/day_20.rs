@@ -1,60 +1,106 @@
-use std::str::FromStr;
+use crate::error::{parse_number, ParseError};
+use crate::solutions::Solution;
 
-pub fn solve_1(nr_presents: &str) -> usize {
-    let nr_presents = usize::from_str(nr_presents).unwrap();
-    let nr_houses = 1_000_000;
+/// The number of houses the elves visit, enough for any puzzle input.
+const NR_HOUSES: usize = 1_000_000;
 
-    let mut houses: Vec<usize> = vec![0; nr_houses];
+pub fn solve_1(nr_presents: &str) -> Result<usize, ParseError> {
+    infinite_elves(&parse(nr_presents)?)
+}
+
+pub fn solve_2(nr_presents: &str) -> Result<usize, ParseError> {
+    lazy_elves(&parse(nr_presents)?)
+}
+
+fn infinite_elves(presents: &Presents) -> Result<usize, ParseError> {
+    let mut houses: Vec<usize> = vec![0; NR_HOUSES];
 
-    for house in 1..nr_houses {
-        for idx in (house..nr_houses).step_by(house) {
+    for house in 1..NR_HOUSES {
+        for idx in (house..NR_HOUSES).step_by(house) {
             houses[idx] += house * 10;
         }
     }
 
-    houses
-        .iter()
-        .enumerate()
-        .find(|(_, &nr_p)| nr_p >= nr_presents)
-        .map(|(house, _)| house)
-        .unwrap()
+    first_house(presents, &houses)
 }
 
-pub fn solve_2(nr_presents: &str) -> usize {
-    let nr_presents = usize::from_str(nr_presents).unwrap();
-    let nr_houses = 1_000_000;
-
-    let mut houses: Vec<usize> = vec![0; nr_houses];
+fn lazy_elves(presents: &Presents) -> Result<usize, ParseError> {
+    let mut houses: Vec<usize> = vec![0; NR_HOUSES];
 
-    for house in 1..nr_houses {
-        for idx in (house..nr_houses.min(51 * house)).step_by(house) {
+    for house in 1..NR_HOUSES {
+        for idx in (house..NR_HOUSES.min(51 * house)).step_by(house) {
             houses[idx] += house * 11;
         }
     }
 
+    first_house(presents, &houses)
+}
+
+fn first_house(presents: &Presents, houses: &[usize]) -> Result<usize, ParseError> {
     houses
         .iter()
-        .enumerate()
-        .find(|(_, &nr_p)| nr_p >= nr_presents)
-        .map(|(house, _)| house)
-        .unwrap()
+        .position(|&nr_p| nr_p >= presents.nr_presents)
+        .ok_or_else(|| {
+            ParseError::new(
+                Day20::DAY,
+                presents.text,
+                presents.text,
+                format!("no house below {NR_HOUSES} gets this many presents"),
+            )
+        })
+}
+
+/// The parsed number of presents, along with its text to point at in errors.
+pub struct Presents<'a> {
+    text: &'a str,
+    nr_presents: usize,
+}
+
+fn parse(nr_presents: &str) -> Result<Presents<'_>, ParseError> {
+    Ok(Presents {
+        text: nr_presents,
+        nr_presents: parse_number(Day20::DAY, nr_presents, nr_presents)?,
+    })
+}
+
+pub struct Day20;
+
+impl Solution for Day20 {
+    const DAY: u8 = 20;
+
+    type Input<'a> = Presents<'a>;
+    type Answer1 = Result<usize, ParseError>;
+    type Answer2 = Result<usize, ParseError>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input.trim())
+    }
+
+    fn part_1(input: &Self::Input<'_>) -> Self::Answer1 {
+        infinite_elves(input)
+    }
+
+    fn part_2(input: &Self::Input<'_>) -> Self::Answer2 {
+        lazy_elves(input)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::util::Input;
 
     #[test]
     fn day_20_part_01_sample() {
-        assert_eq!(1, solve_1("10"));
-        assert_eq!(4, solve_1("70"));
+        assert_eq!(Ok(1), solve_1("10"));
+        assert_eq!(Ok(4), solve_1("70"));
     }
 
     #[test]
     fn day_20_part_01_solution() {
-        let input = include_str!("../../inputs/day_20.txt").trim();
+        let input = Input::load(20).unwrap().text();
 
-        assert_eq!(776_160, solve_1(input));
+        assert_eq!(Ok(776_160), solve_1(input));
     }
 
     #[test]
@@ -64,8 +110,23 @@ mod tests {
 
     #[test]
     fn day_20_part_02_solution() {
-        let input = include_str!("../../inputs/day_20.txt").trim();
+        let input = Input::load(20).unwrap().text();
+
+        assert_eq!(Ok(786_240), solve_2(input));
+    }
+
+    #[test]
+    fn day_20_parse_error() {
+        let error = solve_1("12x").unwrap_err();
+
+        assert_eq!((1, 1), (error.line, error.column));
 
-        assert_eq!(786_240, solve_2(input));
+        let error = solve_1("99999999999").unwrap_err();
+        assert_eq!((1, 1), (error.line, error.column));
+        assert_eq!(
+            "no house below 1000000 gets this many presents",
+            error.reason
+        );
+        assert!(solve_2("99999999999").is_err());
     }
 }
@@ -1,10 +1,19 @@
 use itertools::{iproduct, Itertools};
-use std::str::FromStr;
+
+use crate::error::{parse_number, ParseError};
+use crate::solutions::Solution;
 
 const PLAYER_HP: i16 = 100;
 
-pub fn solve_1(boss: &[&str]) -> i16 {
-    let boss = Boss::new(boss);
+pub fn solve_1(boss: &[&str]) -> Result<i16, ParseError> {
+    cheapest_win(&Boss::new(boss)?)
+}
+
+pub fn solve_2(boss: &[&str]) -> Result<i16, ParseError> {
+    priciest_loss(&Boss::new(boss)?)
+}
+
+fn cheapest_win(boss: &Boss) -> Result<i16, ParseError> {
     let shop = Shop::new();
 
     shop.inventories()
@@ -12,11 +21,10 @@ pub fn solve_1(boss: &[&str]) -> i16 {
         .filter(|inv| boss.wins(inv))
         .map(|inv| inv.iter().map(|i| i.cost).sum::<i16>())
         .min()
-        .unwrap()
+        .ok_or_else(|| boss.error("no inventory beats the boss"))
 }
 
-pub fn solve_2(boss: &[&str]) -> i16 {
-    let boss = Boss::new(boss);
+fn priciest_loss(boss: &Boss) -> Result<i16, ParseError> {
     let shop = Shop::new();
 
     shop.inventories()
@@ -24,27 +32,43 @@ pub fn solve_2(boss: &[&str]) -> i16 {
         .filter(|inv| boss.loses(inv))
         .map(|inv| inv.iter().map(|i| i.cost).sum::<i16>())
         .max()
-        .unwrap()
+        .ok_or_else(|| boss.error("no inventory loses to the boss"))
 }
 
 #[derive(Debug)]
-struct Boss {
+pub struct Boss<'a> {
+    lines: Vec<&'a str>,
     hit_points: i16,
     damage: i16,
     armor: i16,
 }
 
-impl Boss {
-    fn new(boss: &[&str]) -> Self {
-        let hit_points = i16::from_str(&boss[0][12..]).unwrap();
-        let damage = i16::from_str(&boss[1][8..]).unwrap();
-        let armor = i16::from_str(&boss[2][7..]).unwrap();
+impl<'a> Boss<'a> {
+    fn new(boss: &[&'a str]) -> Result<Self, ParseError> {
+        let stat = |idx: usize, name: &str| {
+            let line = boss.get(idx).copied().unwrap_or_default();
+            let value = line.strip_prefix(name).ok_or_else(|| {
+                ParseError::new(Day21::DAY, line, line, format!("expected '{name}N'"))
+            });
+
+            value
+                .and_then(|v| parse_number(Day21::DAY, line, v))
+                .map_err(|e| e.on_line(idx + 1))
+        };
+
+        Ok(Self {
+            lines: boss.to_vec(),
+            hit_points: stat(0, "Hit Points: ")?,
+            damage: stat(1, "Damage: ")?,
+            armor: stat(2, "Armor: ")?,
+        })
+    }
 
-        Self {
-            hit_points,
-            damage,
-            armor,
-        }
+    /// Creates an error pointing at the end of the boss stats.
+    fn error(&self, reason: &str) -> ParseError {
+        let last = self.lines.last().copied().unwrap_or_default();
+        ParseError::new(Day21::DAY, last, &last[last.len()..], reason)
+            .on_line(self.lines.len().max(1))
     }
 
     fn wins(&self, inventory: &[&Item]) -> bool {
@@ -154,11 +178,32 @@ impl<'a> Item<'a> {
     }
 }
 
+pub struct Day21;
+
+impl Solution for Day21 {
+    const DAY: u8 = 21;
+
+    type Input<'a> = Boss<'a>;
+    type Answer1 = Result<i16, ParseError>;
+    type Answer2 = Result<i16, ParseError>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        Boss::new(&input.lines().collect_vec())
+    }
+
+    fn part_1(input: &Self::Input<'_>) -> Self::Answer1 {
+        cheapest_win(input)
+    }
+
+    fn part_2(input: &Self::Input<'_>) -> Self::Answer2 {
+        priciest_loss(input)
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use itertools::Itertools;
-
     use super::*;
+    use crate::util::Input;
 
     #[test]
     fn day_21_part_01_sample() {
@@ -167,11 +212,9 @@ mod tests {
 
     #[test]
     fn day_21_part_01_solution() {
-        let input = include_str!("../../inputs/day_21.txt")
-            .lines()
-            .collect_vec();
+        let input = Input::load(21).unwrap().lines();
 
-        assert_eq!(78, solve_1(&input));
+        assert_eq!(Ok(78), solve_1(&input));
     }
 
     #[test]
@@ -181,10 +224,29 @@ mod tests {
 
     #[test]
     fn day_21_part_02_solution() {
-        let input = include_str!("../../inputs/day_21.txt")
-            .lines()
-            .collect_vec();
+        let input = Input::load(21).unwrap().lines();
+
+        assert_eq!(Ok(148), solve_2(&input));
+    }
+
+    #[test]
+    fn day_21_parse_error() {
+        let error = solve_1(&["Hit Points: 104", "Damage: 8", "Armour: 1"]).unwrap_err();
+
+        assert_eq!((3, 1), (error.line, error.column));
+
+        let error = solve_1(&["Hit Points: 104", "Damage: x"]).unwrap_err();
+
+        assert_eq!((2, 9), (error.line, error.column));
+    }
+
+    #[test]
+    fn day_21_no_fight_to_win_or_lose() {
+        let error = solve_1(&["Hit Points: 30000", "Damage: 100", "Armor: 100"]).unwrap_err();
+        assert_eq!((3, 11), (error.line, error.column));
+        assert_eq!("no inventory beats the boss", error.reason);
 
-        assert_eq!(148, solve_2(&input));
+        let error = solve_2(&["Hit Points: 1", "Damage: 1", "Armor: 0"]).unwrap_err();
+        assert_eq!("no inventory loses to the boss", error.reason);
     }
 }
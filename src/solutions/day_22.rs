@@ -1,187 +1,397 @@
-use std::collections::VecDeque;
+use std::cmp::Reverse;
+use std::fmt::{Display, Formatter};
 use std::ops::Not;
 use std::str::FromStr;
 
-use rustc_hash::FxHashMap;
+use itertools::Itertools;
+use radix_heap::RadixHeapMap;
+use rustc_hash::{FxHashMap, FxHashSet};
+use serde_json::Value;
 
-pub fn solve_1(boss: &[&str]) -> i32 {
-    solve(boss, false)
+use crate::error::{json_error, parse_number, ParseError};
+use crate::solutions::Solution;
+use crate::util::gcd;
+
+pub fn solve_1(boss: &[&str]) -> Result<i32, ParseError> {
+    solve(&Encounter::new(boss)?, &Rules::default())
+}
+
+pub fn solve_2(boss: &[&str]) -> Result<i32, ParseError> {
+    solve(&Encounter::new(boss)?, &Rules::hard())
 }
 
-pub fn solve_2(boss: &[&str]) -> i32 {
-    solve(boss, true)
+fn solve(encounter: &Encounter, rules: &Rules) -> Result<i32, ParseError> {
+    match fight(encounter.boss, &Config::default(), rules) {
+        Some(fight) => Ok(fight.spent_mana),
+        None => {
+            let last = encounter.lines.last().copied().unwrap_or_default();
+            Err(ParseError::new(
+                Day22::DAY,
+                last,
+                &last[last.len()..],
+                "the boss cannot be beaten",
+            )
+            .on_line(encounter.lines.len().max(1)))
+        }
+    }
 }
 
-fn solve(boss: &[&str], hard_mode: bool) -> i32 {
-    let boss = Boss::new(boss);
-    let book = Book::new();
-    let game = Game::new(boss, &book);
+/// Finds the fight in which the player wins while spending the least mana, if the player can win at all.
+///
+/// Games are played in order of spent mana plus the least mana still needed to finish off the boss,
+/// every distinct game only once.
+pub fn fight(boss: Boss, config: &Config, rules: &Rules) -> Option<Fight> {
+    finish(Game::new(boss, &config.player), &config.book, rules)
+}
 
-    let mut to_play: VecDeque<Game> = VecDeque::new();
-    to_play.push_back(game);
+/// The most distinct games played to find a fight, before giving up on it.
+const MAX_GAMES: usize = 2_000_000;
 
-    let mut min_mana = i32::MAX;
+/// Finds the cheapest way to win a fight that is already underway, counting only the mana spent from `start` on.
+///
+/// Spells costing no mana, such as every spell at a mana cost of 0%, can make for endlessly many games
+/// without spending any more mana, so this gives up after playing [`MAX_GAMES`] games.
+pub fn finish(start: Game, book: &Book, rules: &Rules) -> Option<Fight> {
+    let mut seen: Seen = FxHashMap::default();
+    let mut played: FxHashSet<Game> = FxHashSet::default();
+    let mut to_play = RadixHeapMap::new();
 
-    while let Some(game) = to_play.pop_front() {
-        game.play(hard_mode)
-            .into_iter()
-            .for_each(|g| match g.state() {
-                GameState::OnGoing => {
-                    if g.spent_mana < min_mana {
-                        to_play.push_back(g)
-                    }
-                }
-                GameState::Won => min_mana = min_mana.min(g.spent_mana),
-                GameState::Lost => {}
-            });
+    seen.insert(start, (0, None));
+    to_play.push(Reverse(start.lower_bound(book, rules)), start);
+
+    while let Some((_, game)) = to_play.pop() {
+        if played.insert(game).not() {
+            continue;
+        }
+        if played.len() > MAX_GAMES {
+            return None;
+        }
+
+        let spent_mana = seen[&game].0;
+
+        match game.state() {
+            GameState::Won => return Some(Fight::replay(book, &seen, game, spent_mana)),
+            GameState::Lost => continue,
+            GameState::OnGoing => {}
+        }
+
+        for (spell, next) in game.play(book, rules) {
+            let spent_mana = spent_mana + spell.map_or(0, |idx| rules.cost(&book.spells[idx]));
+
+            if seen
+                .get(&next)
+                .is_none_or(|&(least_mana, _)| spent_mana < least_mana)
+            {
+                seen.insert(next, (spent_mana, Some((game, spell))));
+                to_play.push(Reverse(spent_mana + next.lower_bound(book, rules)), next);
+            }
+        }
     }
 
-    min_mana
+    None
+}
+
+/// The least mana spent to reach every game seen, along with the game it followed from and the spell cast.
+type Seen = FxHashMap<Game, (i32, Option<(Game, Option<usize>)>)>;
+
+/// A won fight, turn by turn.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Fight {
+    pub spent_mana: i32,
+    pub turns: Vec<Turn>,
+}
+
+/// The state at the end of a turn, along with the spell cast by the player during that turn.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Turn {
+    pub actor: Actor,
+    pub spell: Option<Spell>,
+    pub player_hp: i32,
+    pub mana: i32,
+    pub boss_hp: i32,
+    /// Spells still in effect, along with the number of turns they last.
+    pub effects: Vec<(Spell, i32)>,
+}
+
+impl Fight {
+    /// Walks back from the winning game to the start of the fight.
+    fn replay(book: &Book, seen: &Seen, winner: Game, spent_mana: i32) -> Self {
+        let mut turns = vec![];
+        let mut game = winner;
+        while let Some(&(_, Some((previous, spell)))) = seen.get(&game) {
+            turns.push(Turn::new(book, &previous, spell, &game));
+            game = previous;
+        }
+        turns.reverse();
+
+        Self { spent_mana, turns }
+    }
+}
+
+impl Turn {
+    /// Describes the turn played in `previous`, casting `spell` from `book` and ending in `game`.
+    pub fn new(book: &Book, previous: &Game, spell: Option<usize>, game: &Game) -> Self {
+        Self {
+            actor: previous.actor,
+            spell: spell.map(|idx| book.spells[idx].clone()),
+            player_hp: game.player_hp,
+            mana: game.mana,
+            boss_hp: game.boss_hp,
+            effects: game
+                .effects(book)
+                .into_iter()
+                .map(|(spell, turns)| (spell.clone(), turns))
+                .sorted()
+                .collect(),
+        }
+    }
+}
+
+impl Display for Fight {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for turn in &self.turns {
+            writeln!(f, "{turn}")?;
+        }
+        write!(f, "Spent {} mana", self.spent_mana)
+    }
+}
+
+impl Display for Turn {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match (self.actor, &self.spell) {
+            (Actor::Player, Some(spell)) => write!(f, "Player casts {:<14}", spell.name)?,
+            (Actor::Player, None) => write!(f, "{:<27}", "Effects")?,
+            (Actor::Boss, _) => write!(f, "{:<27}", "Boss attacks")?,
+        }
+        write!(
+            f,
+            "player {:>3} HP, {:>4} mana | boss {:>3} HP",
+            self.player_hp, self.mana, self.boss_hp
+        )?;
+
+        if self.effects.is_empty().not() {
+            let effects = self
+                .effects
+                .iter()
+                .map(|(spell, turns)| format!("{} ({turns})", spell.name))
+                .join(", ");
+            write!(f, " | {effects}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The parsed boss, along with its lines to point at in errors.
+pub struct Encounter<'a> {
+    lines: Vec<&'a str>,
+    boss: Boss,
+}
+
+impl<'a> Encounter<'a> {
+    fn new(boss: &[&'a str]) -> Result<Self, ParseError> {
+        Ok(Self {
+            lines: boss.to_vec(),
+            boss: Boss::new(boss)?,
+        })
+    }
+
+    pub fn boss(&self) -> Boss {
+        self.boss
+    }
 }
 
 #[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
-struct Boss {
+pub struct Boss {
     hit_points: i32,
     damage: i32,
 }
 
 impl Boss {
-    fn new(boss: &[&str]) -> Self {
-        let hit_points = i32::from_str(&boss[0][12..]).unwrap();
-        let damage = i32::from_str(&boss[1][8..]).unwrap();
+    fn new(boss: &[&str]) -> Result<Self, ParseError> {
+        let stat = |idx: usize, name: &str| {
+            let line = boss.get(idx).copied().unwrap_or_default();
+            let value = line.strip_prefix(name).ok_or_else(|| {
+                ParseError::new(Day22::DAY, line, line, format!("expected '{name}N'"))
+            });
+
+            value
+                .and_then(|v| parse_number(Day22::DAY, line, v))
+                .map_err(|e| e.on_line(idx + 1))
+        };
 
-        Self { hit_points, damage }
+        Ok(Self {
+            hit_points: stat(0, "Hit Points: ")?,
+            damage: stat(1, "Damage: ")?,
+        })
     }
 }
 
-#[derive(Debug, Clone, Eq, PartialEq)]
-struct Game<'a> {
+/// The most spells a book can hold, each having its own effect timer.
+const MAX_SPELLS: usize = 8;
+
+/// The state of a fight at the start of a turn, compact enough to hash and copy around cheaply.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Game {
     actor: Actor,
     mana: i32,
-    spent_mana: i32,
     player_hp: i32,
     boss_hp: i32,
     boss_damage: i32,
-    book: &'a Book,
-    effects: FxHashMap<&'a Spell, i32>,
+    /// The number of turns each spell of the book remains in effect, 0 if not in effect.
+    timers: [u8; MAX_SPELLS],
 }
 
-impl<'a> Game<'a> {
-    fn new(boss: Boss, book: &'a Book) -> Self {
+impl Game {
+    pub fn new(boss: Boss, player: &Player) -> Self {
         Self {
             actor: Actor::Player,
-            mana: 500,
-            spent_mana: 0,
-            player_hp: 50,
+            mana: player.mana,
+            player_hp: player.hit_points,
             boss_hp: boss.hit_points,
             boss_damage: boss.damage,
-            book,
-            effects: Default::default(),
+            timers: [0; MAX_SPELLS],
         }
     }
 
-    fn play(&self, hard_mode: bool) -> Vec<Self> {
+    /// Plays a single turn, returning every possible next game along with the index of the spell the player cast.
+    pub fn play(&self, book: &Book, rules: &Rules) -> Vec<(Option<usize>, Self)> {
         let mut player_mana = self.mana;
         let mut player_armor = 0;
         let mut player_hp = self.player_hp;
         let mut boss_hp = self.boss_hp;
 
-        // First check if hard mode is enables and whether this makes us lose
-        if hard_mode && matches!(self.actor, Actor::Player) {
-            player_hp -= 1;
+        // First drain the player's hit points and check whether this makes us lose
+        if matches!(self.actor, Actor::Player) && rules.drain != 0 {
+            player_hp -= rules.drain;
             if player_hp <= 0 {
                 return vec![];
             }
         }
 
         // Then deal with active effects
-        self.effects.iter().for_each(|(&s, _)| match s {
-            Spell::Shield { armor, .. } => player_armor = *armor,
-            Spell::Poison { damage, .. } => boss_hp -= damage,
-            Spell::Recharge { mana, .. } => player_mana += *mana,
-            _ => unreachable!(),
-        });
+        book.spells
+            .iter()
+            .zip(self.timers)
+            .filter(|(_, turns)| *turns > 0)
+            .for_each(|(s, _)| {
+                player_armor += s.armor;
+                player_hp += s.heal_per_turn;
+                player_mana += s.mana_per_turn;
+                boss_hp -= s.damage_per_turn;
+            });
+
+        // Decrease the timer of all ongoing effects, the ones reaching 0 end
+        let timers = self.timers.map(|turns| turns.saturating_sub(1));
 
         // If the active effect kills the boss, return the winning game
         if boss_hp <= 0 {
-            let mut game = self.clone();
-            game.boss_hp = boss_hp;
-            return vec![game];
+            return vec![(
+                None,
+                Self {
+                    mana: player_mana,
+                    player_hp,
+                    boss_hp,
+                    timers,
+                    ..*self
+                },
+            )];
         }
 
-        // Decrease the timer of all ongoing effects, remove the ones that ended
-        let effects: FxHashMap<&'a Spell, i32> = self
-            .effects
-            .iter()
-            .map(|(&s, &t)| (s, t - 1))
-            .filter(|(_, t)| t > &0)
-            .collect();
-
         match self.actor {
             // Play the turn as the player, using any available spell (enough mana + not already in use)
-            Actor::Player => self
-                .book
+            Actor::Player => book
                 .spells
                 .iter()
-                .filter(|&s| player_mana >= s.cost())
-                .filter(|&s| effects.contains_key(s).not())
-                .map(|s| match s {
-                    Spell::MagicMissile { cost, damage } => Game {
-                        actor: Actor::Boss,
-                        mana: player_mana - cost,
-                        spent_mana: self.spent_mana + cost,
-                        player_hp,
-                        boss_hp: boss_hp - damage,
-                        boss_damage: self.boss_damage,
-                        book: self.book,
-                        effects: effects.clone(),
-                    },
-                    Spell::Drain { cost, damage, heal } => Game {
-                        actor: Actor::Boss,
-                        mana: player_mana - cost,
-                        spent_mana: self.spent_mana + cost,
-                        player_hp: player_hp + heal,
-                        boss_hp: boss_hp - damage,
-                        boss_damage: self.boss_damage,
-                        book: self.book,
-                        effects: effects.clone(),
-                    },
-                    _ => {
-                        let mut effects = effects.clone();
-                        effects.insert(s, s.turns());
-
+                .enumerate()
+                .filter(|(_, s)| player_mana >= rules.cost(s))
+                .filter(|&(idx, _)| timers[idx] == 0)
+                .map(|(idx, s)| {
+                    let mut timers = timers;
+                    timers[idx] = rules.turns(s) as u8;
+
+                    (
+                        Some(idx),
                         Game {
                             actor: Actor::Boss,
-                            mana: player_mana - s.cost(),
-                            spent_mana: self.spent_mana + s.cost(),
-                            player_hp,
-                            boss_hp,
-                            boss_damage: self.boss_damage,
-                            book: self.book,
-                            effects,
-                        }
-                    }
+                            mana: player_mana - rules.cost(s),
+                            player_hp: player_hp + s.heal,
+                            boss_hp: boss_hp - s.damage,
+                            timers,
+                            ..*self
+                        },
+                    )
                 })
                 .collect(),
             // Play the turn as the boss, dealing damage
             Actor::Boss => {
-                let player_hp = player_hp - 1.max(self.boss_damage - player_armor);
-
-                vec![Self {
-                    actor: Actor::Player,
-                    mana: player_mana,
-                    spent_mana: self.spent_mana,
-                    player_hp,
-                    boss_hp,
-                    boss_damage: self.boss_damage,
-                    book: self.book,
-                    effects,
-                }]
+                let player_hp =
+                    player_hp - 1.max(rules.boss_damage(self.boss_damage) - player_armor);
+
+                vec![(
+                    None,
+                    Self {
+                        actor: Actor::Player,
+                        mana: player_mana,
+                        player_hp,
+                        boss_hp,
+                        timers,
+                        ..*self
+                    },
+                )]
             }
         }
     }
 
-    fn state(&self) -> GameState {
+    /// The least mana needed to deal the damage the boss can still take, after ongoing effects ran out.
+    ///
+    /// Casting a spell never lowers this by more than its cost, so games are played in order of total cost.
+    fn lower_bound(&self, book: &Book, rules: &Rules) -> i32 {
+        let pending: i32 = book
+            .spells
+            .iter()
+            .zip(self.timers)
+            .map(|(s, turns)| s.damage_per_turn * turns as i32)
+            .sum();
+        let (cost, damage) = book.cheapest_damage(rules);
+
+        match (self.boss_hp - pending).max(0) as i64 * cost as i64 {
+            _ if damage == 0 => 0,
+            mana => (mana / damage as i64) as i32,
+        }
+    }
+
+    pub fn actor(&self) -> Actor {
+        self.actor
+    }
+
+    pub fn player_hp(&self) -> i32 {
+        self.player_hp
+    }
+
+    pub fn mana(&self) -> i32 {
+        self.mana
+    }
+
+    pub fn boss_hp(&self) -> i32 {
+        self.boss_hp
+    }
+
+    pub fn boss_damage(&self) -> i32 {
+        self.boss_damage
+    }
+
+    /// The spells of `book` in effect, along with the number of turns they last.
+    pub fn effects<'b>(&self, book: &'b Book) -> Vec<(&'b Spell, i32)> {
+        book.spells
+            .iter()
+            .zip(self.timers)
+            .filter(|(_, turns)| *turns > 0)
+            .map(|(spell, turns)| (spell, turns as i32))
+            .collect()
+    }
+
+    pub fn state(&self) -> GameState {
         if self.player_hp > 0 && self.boss_hp > 0 {
             GameState::OnGoing
         } else if self.player_hp > 0 {
@@ -193,90 +403,476 @@ impl<'a> Game<'a> {
 }
 
 #[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
-enum Actor {
+pub enum Actor {
     Player,
     Boss,
 }
 
 #[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
-enum GameState {
+pub enum GameState {
     OnGoing,
     Won,
     Lost,
 }
 
-#[derive(Debug, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
-struct Book {
+/// The stats of the player and the spells they can cast.
+///
+/// Either written as one line per spell, optionally preceded by the player's stats:
+///
+/// ```text
+/// Player: hit_points 50, mana 500
+/// Magic Missile: cost 53, damage 4
+/// Poison: cost 173, turns 6, damage_per_turn 3
+/// ```
+///
+/// Or as JSON, with the same stats:
+///
+/// ```json
+/// {"player": {"hit_points": 50, "mana": 500}, "spells": [{"name": "Magic Missile", "cost": 53, "damage": 4}]}
+/// ```
+///
+/// Stats of the player that are left out keep their value from the puzzle, and so does the book when no spells are listed.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct Config {
+    pub player: Player,
+    pub book: Book,
+}
+
+impl Config {
+    pub fn new(config: &str) -> Result<Self, ParseError> {
+        match config.trim_start().starts_with('{') {
+            true => Self::from_json(config),
+            false => Self::from_text(config),
+        }
+    }
+
+    fn from_text(config: &str) -> Result<Self, ParseError> {
+        let mut player = Player::default();
+        let mut spells = vec![];
+
+        for (idx, line) in config.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let error = |at: &str, reason: String| {
+                ParseError::new(Day22::DAY, line, at, reason).on_line(idx + 1)
+            };
+
+            let (name, stats) = line
+                .split_once(':')
+                .ok_or_else(|| error(line, "expected 'NAME: STAT N, STAT N, ...'".to_string()))?;
+            let name = name.trim();
+            let mut spell = Spell::new(name);
+
+            for stat in stats.split(',').map(str::trim) {
+                let (key, value) = stat
+                    .split_once(' ')
+                    .ok_or_else(|| error(stat, "expected 'STAT N'".to_string()))?;
+                let value =
+                    parse_number(Day22::DAY, line, value.trim()).map_err(|e| e.on_line(idx + 1))?;
+
+                match name {
+                    "Player" => player.set(key, value),
+                    _ => spell.set(key, value),
+                }
+                .map_err(|reason| error(stat, reason))?;
+            }
+
+            match name {
+                "Player" => player.check(),
+                _ => Book::add(&mut spells, spell),
+            }
+            .map_err(|reason| error(name, reason))?;
+        }
+
+        Ok(Self {
+            player,
+            book: Book::new(spells),
+        })
+    }
+
+    fn from_json(config: &str) -> Result<Self, ParseError> {
+        let json = Value::from_str(config).map_err(|e| json_error(Day22::DAY, config, &e))?;
+        // The JSON values no longer know where they came from, so point at the start of the config
+        let error = |reason: String| {
+            let text = config.lines().next().unwrap_or_default();
+            ParseError::new(Day22::DAY, text, text, reason)
+        };
+        let set =
+            |json: &Value, what: &str, apply: &mut dyn FnMut(&str, i32) -> Result<(), String>| {
+                let stats = json
+                    .as_object()
+                    .ok_or_else(|| error(format!("{what}: expected an object")))?;
+
+                stats
+                    .iter()
+                    .filter(|(key, _)| key.as_str() != "name")
+                    .try_for_each(|(key, value)| {
+                        let value = value
+                            .as_i64()
+                            .and_then(|v| i32::try_from(v).ok())
+                            .ok_or_else(|| format!("expected a number for '{key}'"))?;
+                        apply(key, value)
+                    })
+                    .map_err(|reason| error(format!("{what}: {reason}")))
+            };
+
+        let config = json
+            .as_object()
+            .ok_or_else(|| error("expected an object".to_string()))?;
+        if let Some(key) = config.keys().find(|&k| k != "player" && k != "spells") {
+            return Err(error(format!("unknown key '{key}'")));
+        }
+
+        let mut player = Player::default();
+        if let Some(json) = config.get("player") {
+            set(json, "player", &mut |stat, value| player.set(stat, value))?;
+            player
+                .check()
+                .map_err(|reason| error(format!("player: {reason}")))?;
+        }
+
+        let mut spells = vec![];
+        if let Some(json) = config.get("spells") {
+            let json = json
+                .as_array()
+                .ok_or_else(|| error("spells: expected an array".to_string()))?;
+
+            for (idx, json) in json.iter().enumerate() {
+                let name = json
+                    .get("name")
+                    .and_then(Value::as_str)
+                    .ok_or_else(|| error(format!("spell {}: expected a name", idx + 1)))?;
+                let mut spell = Spell::new(name);
+
+                set(json, name, &mut |stat, value| spell.set(stat, value))?;
+                Book::add(&mut spells, spell)
+                    .map_err(|reason| error(format!("{name}: {reason}")))?;
+            }
+        }
+
+        Ok(Self {
+            player,
+            book: Book::new(spells),
+        })
+    }
+}
+
+/// The stats the player starts the fight with.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Player {
+    pub hit_points: i32,
+    pub mana: i32,
+}
+
+impl Default for Player {
+    fn default() -> Self {
+        Self {
+            hit_points: 50,
+            mana: 500,
+        }
+    }
+}
+
+impl Player {
+    fn set(&mut self, stat: &str, value: i32) -> Result<(), String> {
+        *match stat {
+            "hit_points" => &mut self.hit_points,
+            "mana" => &mut self.mana,
+            _ => return Err(format!("unknown stat '{stat}'")),
+        } = non_negative(stat, value)?;
+
+        Ok(())
+    }
+
+    fn check(&self) -> Result<(), String> {
+        match self.hit_points {
+            0 => Err("expected the player to have hit points".to_string()),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Book {
     spells: Vec<Spell>,
 }
 
-impl Book {
-    fn new() -> Self {
+impl Default for Book {
+    fn default() -> Self {
+        let spell = |name: &str, cost| Spell {
+            name: name.to_string(),
+            cost,
+            ..Default::default()
+        };
+
         Self {
             spells: vec![
-                Spell::MagicMissile {
-                    cost: 53,
+                Spell {
                     damage: 4,
+                    ..spell("Magic Missile", 53)
                 },
-                Spell::Drain {
-                    cost: 73,
+                Spell {
                     damage: 2,
                     heal: 2,
+                    ..spell("Drain", 73)
                 },
-                Spell::Shield {
-                    cost: 113,
-                    armor: 7,
+                Spell {
                     turns: 6,
+                    armor: 7,
+                    ..spell("Shield", 113)
                 },
-                Spell::Poison {
-                    cost: 173,
-                    damage: 3,
+                Spell {
                     turns: 6,
+                    damage_per_turn: 3,
+                    ..spell("Poison", 173)
                 },
-                Spell::Recharge {
-                    cost: 229,
-                    mana: 101,
+                Spell {
                     turns: 5,
+                    mana_per_turn: 101,
+                    ..spell("Recharge", 229)
                 },
             ],
         }
     }
 }
 
-#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
-enum Spell {
-    MagicMissile { cost: i32, damage: i32 },
-    Drain { cost: i32, damage: i32, heal: i32 },
-    Shield { cost: i32, armor: i32, turns: i32 },
-    Poison { cost: i32, damage: i32, turns: i32 },
-    Recharge { cost: i32, mana: i32, turns: i32 },
+impl Book {
+    /// Creates a book holding `spells`, or the book of the puzzle when there are none.
+    fn new(spells: Vec<Spell>) -> Self {
+        match spells.is_empty() {
+            true => Self::default(),
+            false => Self { spells },
+        }
+    }
+
+    pub fn spells(&self) -> &[Spell] {
+        &self.spells
+    }
+
+    /// Checks `spell` before adding it to `spells`.
+    fn add(spells: &mut Vec<Spell>, spell: Spell) -> Result<(), String> {
+        spell.check()?;
+        if spells.iter().any(|s| s.name == spell.name) {
+            return Err("spell is listed twice".to_string());
+        }
+        if spells.len() == MAX_SPELLS {
+            return Err(format!("a book holds at most {MAX_SPELLS} spells"));
+        }
+
+        spells.push(spell);
+        Ok(())
+    }
+
+    /// The cost and total damage of the spell dealing damage for the least mana.
+    fn cheapest_damage(&self, rules: &Rules) -> (i32, i32) {
+        self.spells
+            .iter()
+            .map(|s| (rules.cost(s), s.damage + s.damage_per_turn * rules.turns(s)))
+            .filter(|&(_, damage)| damage > 0)
+            .min_by(|&(c1, d1), &(c2, d2)| (c1 as i64 * d2 as i64).cmp(&(c2 as i64 * d1 as i64)))
+            .unwrap_or((0, 0))
+    }
+}
+
+/// A spell acting once when cast, and every turn after that for as long as it remains in effect.
+#[derive(Debug, Clone, Default, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub struct Spell {
+    pub name: String,
+    pub cost: i32,
+    /// Damage dealt to the boss when cast.
+    pub damage: i32,
+    /// Hit points healed when cast.
+    pub heal: i32,
+    /// Number of turns the spell remains in effect, 0 if it only acts when cast.
+    pub turns: i32,
+    pub armor: i32,
+    pub damage_per_turn: i32,
+    pub heal_per_turn: i32,
+    pub mana_per_turn: i32,
 }
 
 impl Spell {
-    fn cost(&self) -> i32 {
-        match self {
-            Spell::MagicMissile { cost, .. } => *cost,
-            Spell::Drain { cost, .. } => *cost,
-            Spell::Shield { cost, .. } => *cost,
-            Spell::Poison { cost, .. } => *cost,
-            Spell::Recharge { cost, .. } => *cost,
+    fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            ..Default::default()
         }
     }
-    fn turns(&self) -> i32 {
-        match self {
-            Spell::Shield { turns, .. } => *turns,
-            Spell::Poison { turns, .. } => *turns,
-            Spell::Recharge { turns, .. } => *turns,
-            _ => unreachable!(),
+
+    fn set(&mut self, stat: &str, value: i32) -> Result<(), String> {
+        *match stat {
+            "cost" => &mut self.cost,
+            "damage" => &mut self.damage,
+            "heal" => &mut self.heal,
+            "turns" => &mut self.turns,
+            "armor" => &mut self.armor,
+            "damage_per_turn" => &mut self.damage_per_turn,
+            "heal_per_turn" => &mut self.heal_per_turn,
+            "mana_per_turn" => &mut self.mana_per_turn,
+            _ => return Err(format!("unknown stat '{stat}'")),
+        } = non_negative(stat, value)?;
+
+        Ok(())
+    }
+
+    fn check(&self) -> Result<(), String> {
+        let effects = [
+            self.armor,
+            self.damage_per_turn,
+            self.heal_per_turn,
+            self.mana_per_turn,
+        ];
+
+        match self.turns {
+            0 if effects.iter().any(|&e| e > 0) => {
+                Err("expected effects to last at least 1 turn".to_string())
+            }
+            // Casting it over and over again would never end the fight
+            _ if self.cost == 0 && self.heal + self.heal_per_turn + self.mana_per_turn > 0 => {
+                Err("expected a spell that heals or restores mana to cost mana".to_string())
+            }
+            turns if turns > u8::MAX as i32 => {
+                Err(format!("effects last at most {} turns", u8::MAX))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Difficulty modifiers of a fight, applying one set of rules on top of another makes them add up.
+///
+/// Scaling by a percentage rounds down, so a spell costing 53 mana costs 79 mana at 150%.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Rules {
+    /// Hit points the player loses at the start of each of their turns.
+    drain: i32,
+    /// Fraction of the boss's damage it actually deals.
+    boss_damage: (u64, u64),
+    /// Fraction of the cost of each spell the player actually pays.
+    mana_cost: (u64, u64),
+    /// Turns added to the duration of every spell with effects, taken away when negative.
+    effect_turns: i32,
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Self {
+            drain: 0,
+            boss_damage: (1, 1),
+            mana_cost: (1, 1),
+            effect_turns: 0,
         }
     }
 }
 
+impl Rules {
+    /// The rules of part 2, where the player loses 1 hit point at the start of each of their turns.
+    pub fn hard() -> Self {
+        Self::default().with_drain(1)
+    }
+
+    pub fn with_drain(self, hit_points: i32) -> Self {
+        Self {
+            drain: self.drain + hit_points,
+            ..self
+        }
+    }
+
+    pub fn with_boss_damage(self, percent: u32) -> Self {
+        Self {
+            boss_damage: Self::scale(self.boss_damage, (percent as u64, 100)),
+            ..self
+        }
+    }
+
+    pub fn with_mana_cost(self, percent: u32) -> Self {
+        Self {
+            mana_cost: Self::scale(self.mana_cost, (percent as u64, 100)),
+            ..self
+        }
+    }
+
+    pub fn with_effect_turns(self, turns: i32) -> Self {
+        Self {
+            effect_turns: self.effect_turns + turns,
+            ..self
+        }
+    }
+
+    /// Applies `other` on top of these rules.
+    pub fn and(self, other: Rules) -> Self {
+        Self {
+            drain: self.drain + other.drain,
+            boss_damage: Self::scale(self.boss_damage, other.boss_damage),
+            mana_cost: Self::scale(self.mana_cost, other.mana_cost),
+            effect_turns: self.effect_turns + other.effect_turns,
+        }
+    }
+
+    /// The mana the player pays to cast `spell`.
+    pub fn cost(&self, spell: &Spell) -> i32 {
+        Self::apply(spell.cost, self.mana_cost)
+    }
+
+    /// The number of turns `spell` remains in effect when cast.
+    pub fn turns(&self, spell: &Spell) -> i32 {
+        match spell.turns {
+            0 => 0,
+            turns => (turns + self.effect_turns).clamp(0, u8::MAX as i32),
+        }
+    }
+
+    /// The damage dealt by a boss with `damage`, before the player's armor.
+    pub fn boss_damage(&self, damage: i32) -> i32 {
+        Self::apply(damage, self.boss_damage)
+    }
+
+    fn scale((n1, d1): (u64, u64), (n2, d2): (u64, u64)) -> (u64, u64) {
+        let (n, d) = (n1 * n2, d1 * d2);
+        let gcd = gcd(n, d);
+
+        (n / gcd, d / gcd)
+    }
+
+    fn apply(value: i32, (numerator, denominator): (u64, u64)) -> i32 {
+        (value as i128 * numerator as i128 / denominator as i128).clamp(0, i32::MAX as i128) as i32
+    }
+}
+
+fn non_negative(stat: &str, value: i32) -> Result<i32, String> {
+    match value {
+        ..0 => Err(format!("expected '{stat}' not to be negative")),
+        _ => Ok(value),
+    }
+}
+
+pub struct Day22;
+
+impl Solution for Day22 {
+    const DAY: u8 = 22;
+
+    type Input<'a> = Encounter<'a>;
+    type Answer1 = Result<i32, ParseError>;
+    type Answer2 = Result<i32, ParseError>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        Encounter::new(&input.lines().collect_vec())
+    }
+
+    fn part_1(input: &Self::Input<'_>) -> Self::Answer1 {
+        solve(input, &Rules::default())
+    }
+
+    fn part_2(input: &Self::Input<'_>) -> Self::Answer2 {
+        solve(input, &Rules::hard())
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use itertools::Itertools;
-
     use super::*;
+    use crate::util::Input;
 
     #[test]
     fn day_22_part_01_sample() {
@@ -285,11 +881,9 @@ mod tests {
 
     #[test]
     fn day_22_part_01_solution() {
-        let input = include_str!("../../inputs/day_22.txt")
-            .lines()
-            .collect_vec();
+        let input = Input::load(22).unwrap().lines();
 
-        assert_eq!(953, solve_1(&input));
+        assert_eq!(Ok(953), solve_1(&input));
     }
 
     #[test]
@@ -299,10 +893,130 @@ mod tests {
 
     #[test]
     fn day_22_part_02_solution() {
-        let input = include_str!("../../inputs/day_22.txt")
-            .lines()
-            .collect_vec();
+        let input = Input::load(22).unwrap().lines();
+
+        assert_eq!(Ok(1_289), solve_2(&input));
+    }
+
+    #[test]
+    fn day_22_fight() {
+        let boss = Boss::new(&["Hit Points: 24", "Damage: 15"]).unwrap();
+        let fight = fight(boss, &Config::default(), &Rules::default()).unwrap();
+
+        assert_eq!(173 + 2 * 53, fight.spent_mana);
+        assert_eq!(
+            vec![
+                "Player casts Poison        player  50 HP,  327 mana | boss  24 HP | Poison (6)",
+                "Boss attacks               player  35 HP,  327 mana | boss  21 HP | Poison (5)",
+                "Player casts Magic Missile player  35 HP,  274 mana | boss  14 HP | Poison (4)",
+                "Boss attacks               player  20 HP,  274 mana | boss  11 HP | Poison (3)",
+                "Player casts Magic Missile player  20 HP,  221 mana | boss   4 HP | Poison (2)",
+                "Boss attacks               player   5 HP,  221 mana | boss   1 HP | Poison (1)",
+                "Effects                    player   5 HP,  221 mana | boss  -2 HP",
+                "Spent 279 mana",
+            ],
+            fight.to_string().lines().collect_vec()
+        );
+    }
+
+    #[test]
+    fn day_22_strong_boss() {
+        assert_eq!(Ok(1_824), solve_1(&["Hit Points: 71", "Damage: 10"]));
+        assert_eq!(Ok(1_990), solve_1(&["Hit Points: 100", "Damage: 5"]));
+
+        let error = solve_1(&["Hit Points: 5000", "Damage: 60"]).unwrap_err();
+        assert_eq!((2, 11), (error.line, error.column));
+        assert_eq!("the boss cannot be beaten", error.reason);
+    }
+
+    #[test]
+    fn day_22_config() {
+        let text = [
+            "Player: hit_points 50, mana 500",
+            "",
+            "Magic Missile: cost 53, damage 4",
+            "Drain: cost 73, damage 2, heal 2",
+            "Shield: cost 113, turns 6, armor 7",
+            "Poison: cost 173, turns 6, damage_per_turn 3",
+            "Recharge: cost 229, turns 5, mana_per_turn 101",
+        ];
+        assert_eq!(Ok(Config::default()), Config::new(&text.join("\n")));
+
+        let boss = Boss::new(&["Hit Points: 13", "Damage: 8"]).unwrap();
+        let config = Config::new(r#"{"player": {"hit_points": 10, "mana": 250}}"#).unwrap();
+        assert_eq!(
+            Some(173 + 53),
+            fight(boss, &config, &Rules::default()).map(|f| f.spent_mana)
+        );
+
+        let config = Config::new(
+            r#"{
+                "player": {"hit_points": 10},
+                "spells": [
+                    {"name": "Fireball", "cost": 10, "damage": 3},
+                    {"name": "Regenerate", "cost": 5, "turns": 2, "heal_per_turn": 5}
+                ]
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            Some(5 * 10 + 12 * 5),
+            fight(boss, &config, &Rules::default()).map(|f| f.spent_mana)
+        );
+    }
+
+    #[test]
+    fn day_22_config_error() {
+        let error = Config::new("Player: hit_points 10\nFireball: cost 10, dammage 3").unwrap_err();
+        assert_eq!((2, 20), (error.line, error.column));
+
+        let error = Config::new("Fireball: cost 10\nFireball: cost 20").unwrap_err();
+        assert_eq!((2, 1), (error.line, error.column));
+
+        let error = Config::new(r#"{"spells": [{"name": "Fireball", "cost": -1}]}"#).unwrap_err();
+        assert_eq!("Fireball: expected 'cost' not to be negative", error.reason);
+
+        let error =
+            Config::new("Free: cost 0, turns 1, heal_per_turn 20, mana_per_turn 1").unwrap_err();
+        assert_eq!(
+            "expected a spell that heals or restores mana to cost mana",
+            error.reason
+        );
+
+        let error = Config::new("{\n  \"spells\": [\n}").unwrap_err();
+        assert_eq!((3, 1), (error.line, error.column));
+    }
+
+    #[test]
+    fn day_22_rules() {
+        assert_eq!(
+            Rules::default(),
+            Rules::default().with_mana_cost(200).with_mana_cost(50)
+        );
+        assert_eq!(
+            Rules::default().with_drain(2).with_effect_turns(1),
+            Rules::hard().and(Rules::hard().with_effect_turns(1))
+        );
+
+        let book = Book::default();
+        let (missile, poison) = (&book.spells()[0], &book.spells()[3]);
+        let rules = Rules::default().with_mana_cost(150).with_effect_turns(2);
+        assert_eq!((79, 0), (rules.cost(missile), rules.turns(missile)));
+        assert_eq!((259, 8), (rules.cost(poison), rules.turns(poison)));
+
+        let boss = Boss::new(&["Hit Points: 24", "Damage: 15"]).unwrap();
+        let spent = |rules: Rules| fight(boss, &Config::default(), &rules).map(|f| f.spent_mana);
+        assert_eq!(Some(173 + 2 * 53), spent(Rules::hard()));
+        assert_eq!(Some(259 + 2 * 79), spent(Rules::hard().with_mana_cost(150)));
+        assert_eq!(None, spent(Rules::default().with_boss_damage(200)));
+        assert_eq!(None, spent(Rules::default().with_drain(10)));
+        assert_eq!(Some(0), spent(Rules::default().with_mana_cost(0)));
+    }
+
+    #[test]
+    fn day_22_parse_error() {
+        let error = solve_1(&["Hit Points: 55"]).unwrap_err();
 
-        assert_eq!(1_289, solve_2(&input));
+        assert_eq!((2, 1), (error.line, error.column));
     }
 }
@@ -1,81 +1,388 @@
-use std::str::FromStr;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{Display, Formatter};
+use std::ops::Not;
 
-use rustc_hash::FxHashMap;
+use itertools::Itertools;
+use rustc_hash::FxHashSet;
 
-pub fn solve_1(instructions: &[&str]) -> u32 {
-    solve(instructions, 0)
+use crate::error::{parse_number, ParseError};
+use crate::solutions::Solution;
+
+pub fn solve_1(instructions: &[&str]) -> Result<u64, ParseError> {
+    Ok(solve(&assemble(instructions)?, 0))
 }
 
-pub fn solve_2(instructions: &[&str]) -> u32 {
-    solve(instructions, 1)
+pub fn solve_2(instructions: &[&str]) -> Result<u64, ParseError> {
+    Ok(solve(&assemble(instructions)?, 1))
 }
 
-fn solve(instructions: &[&str], register_a: u32) -> u32 {
-    let mut computer = Computer::new(instructions, register_a);
+fn solve(instructions: &[Instruction], register_a: u64) -> u64 {
+    let mut computer = Computer::new(instructions)
+        .with_register(Register::A, register_a)
+        .with_optimisations();
     computer.run();
-    computer.registers[&Register::B]
+    computer.register(Register::B)
 }
 
+/// Virtual machine running a program of [`Instruction`]s, one step at a time if needed.
 #[derive(Debug, Clone, Eq, PartialEq)]
-struct Computer {
+pub struct Computer {
     instructions: Vec<Instruction>,
-    registers: FxHashMap<Register, u32>,
+    registers: [u64; 2],
     ip: usize,
+    steps: usize,
+    max_steps: Option<usize>,
+    breakpoints: FxHashSet<usize>,
+    paused_at: Option<usize>,
+    trace: Option<Vec<Step>>,
+    idioms: Option<Vec<Option<Idiom>>>,
+}
+
+/// A single executed instruction, along with the registers right after executing it.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Step {
+    pub ip: usize,
+    pub instruction: Instruction,
+    pub registers: [u64; 2],
+}
+
+/// State of the [`Computer`] after a step or a run.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Status {
+    /// The next instruction can be executed.
+    Ready,
+    /// The instruction pointer left the program, which is how a program ends.
+    Exited,
+    /// Paused before executing the instruction at a breakpoint, running again resumes.
+    Breakpoint { ip: usize },
+    /// Stopped after executing the maximum number of steps.
+    StepLimit { steps: usize },
+    /// The instruction at `ip` overflows its register, and is not executed.
+    Overflow { ip: usize },
 }
 
 impl Computer {
-    fn new(instructions: &[&str], register_a: u32) -> Self {
+    pub fn new(instructions: &[Instruction]) -> Self {
         Self {
-            instructions: instructions.iter().map(|&i| Instruction::new(i)).collect(),
-            registers: [(Register::A, register_a), (Register::B, 0)]
-                .into_iter()
-                .collect(),
+            instructions: instructions.to_vec(),
+            registers: [0; 2],
             ip: 0,
+            steps: 0,
+            max_steps: None,
+            breakpoints: FxHashSet::default(),
+            paused_at: None,
+            trace: None,
+            idioms: None,
         }
     }
 
-    fn run(&mut self) {
+    pub fn with_register(mut self, register: Register, value: u64) -> Self {
+        self.registers[register as usize] = value;
+        self
+    }
+
+    /// Guards against programs that never end, [`Computer::run`] stopping after `steps` steps.
+    pub fn with_max_steps(mut self, steps: usize) -> Self {
+        self.max_steps = Some(steps);
+        self
+    }
+
+    pub fn with_breakpoint(mut self, ip: usize) -> Self {
+        self.breakpoints.insert(ip);
+        self
+    }
+
+    /// Records every executed instruction, see [`Computer::trace`].
+    pub fn with_trace(mut self) -> Self {
+        self.trace = Some(vec![]);
+        self
+    }
+
+    /// Executes the loop idioms found by [`Idiom::find`] in one go when running,
+    /// unless tracing or a breakpoint inside the idiom requires going step by step.
+    ///
+    /// Registers, steps and status always end up the same as without optimising,
+    /// idioms that would overflow, loop forever or exceed the maximum number of steps
+    /// are simply executed step by step.
+    pub fn with_optimisations(mut self) -> Self {
+        self.idioms = Some(Idiom::find(&self.instructions));
+        self
+    }
+
+    pub fn register(&self, register: Register) -> u64 {
+        self.registers[register as usize]
+    }
+
+    pub fn ip(&self) -> usize {
+        self.ip
+    }
+
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    /// Every executed instruction so far, empty unless created [`Computer::with_trace`].
+    pub fn trace(&self) -> &[Step] {
+        self.trace.as_deref().unwrap_or_default()
+    }
+
+    /// Runs until the program exits, a breakpoint or the maximum number of steps is hit,
+    /// or an instruction overflows.
+    pub fn run(&mut self) -> Status {
         loop {
             if self.ip >= self.instructions.len() {
-                return;
+                return Status::Exited;
+            }
+            if self.breakpoints.contains(&self.ip) && self.paused_at != Some(self.ip) {
+                self.paused_at = Some(self.ip);
+                return Status::Breakpoint { ip: self.ip };
+            }
+            if self.max_steps.is_some_and(|max| self.steps >= max) {
+                return Status::StepLimit { steps: self.steps };
+            }
+            if self.fast_forward() {
+                continue;
             }
 
-            let instruction = &self.instructions[self.ip];
-            match instruction {
-                Instruction::Half { r } => {
-                    *self.registers.get_mut(r).unwrap() /= 2;
-                    self.ip += 1;
-                }
-                Instruction::Triple { r } => {
-                    *self.registers.get_mut(r).unwrap() *= 3;
-                    self.ip += 1;
-                }
-                Instruction::Inc { r } => {
-                    *self.registers.get_mut(r).unwrap() += 1;
-                    self.ip += 1;
-                }
-                Instruction::Jump { offset } => self.ip = (self.ip as i32 + offset) as usize,
-                Instruction::JumpIfEven { r, offset } => {
-                    if *self.registers.get_mut(r).unwrap() % 2 == 0 {
-                        self.ip = (self.ip as i32 + offset) as usize
-                    } else {
-                        self.ip += 1;
-                    }
-                }
-                Instruction::JumpIfOne { r, offset } => {
-                    if *self.registers.get_mut(r).unwrap() == 1 {
-                        self.ip = (self.ip as i32 + offset) as usize
-                    } else {
-                        self.ip += 1;
+            match self.step() {
+                Status::Ready => {}
+                status => return status,
+            }
+        }
+    }
+
+    /// Executes the idiom starting at the instruction pointer at once, if there is one and it is safe to do so.
+    fn fast_forward(&mut self) -> bool {
+        let Some(idiom) = self.idioms.as_ref().and_then(|idioms| idioms[self.ip]) else {
+            return false;
+        };
+        // Loops come back to their first instruction, so a breakpoint there is hit again when resuming
+        let first = match idiom {
+            Idiom::Collatz { .. } => self.ip,
+            Idiom::Add { .. } | Idiom::Triple { .. } => self.ip + 1,
+        };
+        if self.trace.is_some()
+            || (first..self.ip + idiom.span()).any(|ip| self.breakpoints.contains(&ip))
+        {
+            return false;
+        }
+
+        let Some((registers, ip, steps)) = idiom.execute(self.registers, self.ip) else {
+            return false;
+        };
+        if self.max_steps.is_some_and(|max| self.steps + steps > max) {
+            return false;
+        }
+
+        self.registers = registers;
+        self.ip = ip;
+        self.steps += steps;
+        self.paused_at = None;
+
+        true
+    }
+
+    /// Executes the single instruction at the instruction pointer, ignoring breakpoints and step limits.
+    pub fn step(&mut self) -> Status {
+        let Some(&instruction) = self.instructions.get(self.ip) else {
+            return Status::Exited;
+        };
+
+        let ip = self.ip;
+        let jump = |offset: i32| ip.checked_add_signed(offset as isize).unwrap_or(usize::MAX);
+        let value = |r: Register| self.registers[r as usize];
+        let (write, next) = match instruction {
+            Instruction::Half { r } => (Some((r, value(r).checked_div(2))), ip + 1),
+            Instruction::Triple { r } => (Some((r, value(r).checked_mul(3))), ip + 1),
+            Instruction::Inc { r } => (Some((r, value(r).checked_add(1))), ip + 1),
+            Instruction::Jump { offset } => (None, jump(offset)),
+            Instruction::JumpIfEven { r, offset } if value(r) % 2 == 0 => (None, jump(offset)),
+            Instruction::JumpIfOne { r, offset } if value(r) == 1 => (None, jump(offset)),
+            Instruction::JumpIfEven { .. } | Instruction::JumpIfOne { .. } => (None, ip + 1),
+        };
+
+        if let Some((r, result)) = write {
+            let Some(result) = result else {
+                return Status::Overflow { ip };
+            };
+            self.registers[r as usize] = result;
+        }
+        self.ip = next;
+        self.steps += 1;
+        self.paused_at = None;
+        if let Some(trace) = &mut self.trace {
+            trace.push(Step {
+                ip,
+                instruction,
+                registers: self.registers,
+            });
+        }
+
+        match self.ip < self.instructions.len() {
+            true => Status::Ready,
+            false => Status::Exited,
+        }
+    }
+}
+
+/// Both computers of a [`cross_check`] that did not agree.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Mismatch {
+    pub naive: Computer,
+    pub optimised: Computer,
+}
+
+/// Runs the computer both step by step and with optimisations, resuming after every breakpoint
+/// until the program exits, hits the maximum number of steps or overflows.
+/// Returns the optimised computer when both agree on the status, registers, instruction pointer and steps
+/// after every run.
+pub fn cross_check(computer: Computer) -> Result<Computer, Box<Mismatch>> {
+    let mut naive = Computer {
+        idioms: None,
+        ..computer.clone()
+    };
+    let mut optimised = computer.with_optimisations();
+    let state = |c: &Computer| (c.registers, c.ip, c.steps);
+
+    loop {
+        let statuses = (naive.run(), optimised.run());
+        if statuses.0 != statuses.1 || state(&naive) != state(&optimised) {
+            return Err(Box::new(Mismatch { naive, optimised }));
+        }
+        if matches!(statuses.0, Status::Breakpoint { .. }).not() {
+            return Ok(optimised);
+        }
+    }
+}
+
+/// A common sequence of instructions that can be executed at once, rather than step by step.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Idiom {
+    /// `n` times `inc r`.
+    Add { r: Register, n: usize },
+    /// `n` times `tpl r`.
+    Triple { r: Register, n: usize },
+    /// Counts the steps it takes `r` to reach 1 in the Collatz sequence in `counter`,
+    /// jumping `exit` instructions from the start of the loop when done:
+    /// ```text
+    /// jio r, exit
+    /// inc counter
+    /// jie r, +4
+    /// tpl r
+    /// inc r
+    /// jmp +2
+    /// hlf r
+    /// jmp -7
+    /// ```
+    Collatz {
+        r: Register,
+        counter: Register,
+        exit: i32,
+    },
+}
+
+impl Idiom {
+    /// Finds the idioms in the program, by the instruction they start at.
+    ///
+    /// Instructions that are jumped to from outside an idiom are never part of it,
+    /// so jumping into the middle of an idiom simply executes the program step by step.
+    pub fn find(instructions: &[Instruction]) -> Vec<Option<Idiom>> {
+        let len = instructions.len();
+        let jumps = instructions
+            .iter()
+            .enumerate()
+            .filter_map(|(ip, i)| Some((ip, target(ip, i, len)??)))
+            .collect_vec();
+        let enclosed = |start: usize, end: usize| {
+            jumps
+                .iter()
+                .all(|&(from, to)| (start < to && to < end).not() || (start..end).contains(&from))
+        };
+
+        let mut idioms = vec![None; len];
+        let mut ip = 0;
+        while ip < len {
+            let idiom = Self::collatz(&instructions[ip..])
+                .or_else(|| Self::repeated(&instructions[ip..]))
+                .filter(|idiom| enclosed(ip, ip + idiom.span()));
+
+            idioms[ip] = idiom;
+            ip += idiom.map_or(1, |idiom| idiom.span());
+        }
+
+        idioms
+    }
+
+    fn collatz(instructions: &[Instruction]) -> Option<Idiom> {
+        use Instruction::*;
+
+        match *instructions.get(..8)? {
+            [JumpIfOne { r, offset: exit }, Inc { r: counter }, JumpIfEven { r: r1, offset: 4 }, Triple { r: r2 }, Inc { r: r3 }, Jump { offset: 2 }, Half { r: r4 }, Jump { offset: -7 }]
+                if [r1, r2, r3, r4].iter().all(|&x| x == r)
+                    && counter != r
+                    && (0..8).contains(&exit).not() =>
+            {
+                Some(Idiom::Collatz { r, counter, exit })
+            }
+            _ => None,
+        }
+    }
+
+    fn repeated(instructions: &[Instruction]) -> Option<Idiom> {
+        let first = *instructions.first()?;
+        let n = instructions.iter().take_while(|&&i| i == first).count();
+
+        match first {
+            Instruction::Inc { r } if n > 1 => Some(Idiom::Add { r, n }),
+            Instruction::Triple { r } if n > 1 => Some(Idiom::Triple { r, n }),
+            _ => None,
+        }
+    }
+
+    /// Number of instructions making up the idiom.
+    pub fn span(&self) -> usize {
+        match *self {
+            Idiom::Add { n, .. } => n,
+            Idiom::Triple { n, .. } => n,
+            Idiom::Collatz { .. } => 8,
+        }
+    }
+
+    /// Registers, instruction pointer and number of steps after executing the idiom starting at `ip`,
+    /// or `None` when it overflows or never ends.
+    fn execute(&self, mut registers: [u64; 2], ip: usize) -> Option<([u64; 2], usize, usize)> {
+        match *self {
+            Idiom::Add { r, n } => {
+                registers[r as usize] = registers[r as usize].checked_add(n as u64)?;
+                Some((registers, ip + n, n))
+            }
+            Idiom::Triple { r, n } => {
+                let factor = 3u64.checked_pow(n as u32)?;
+                registers[r as usize] = registers[r as usize].checked_mul(factor)?;
+                Some((registers, ip + n, n))
+            }
+            Idiom::Collatz { r, counter, exit } => {
+                let (mut value, mut count, mut steps) = (registers[r as usize], 0u64, 1);
+                while value != 1 {
+                    match value {
+                        0 => return None,
+                        v if v % 2 == 0 => (value, steps) = (v / 2, steps + 5),
+                        v => (value, steps) = (v.checked_mul(3)?.checked_add(1)?, steps + 7),
                     }
+                    count += 1;
                 }
+
+                registers[r as usize] = value;
+                registers[counter as usize] = registers[counter as usize].checked_add(count)?;
+                let ip = ip.checked_add_signed(exit as isize).unwrap_or(usize::MAX);
+                Some((registers, ip, steps))
             }
         }
     }
 }
 
 #[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
-enum Instruction {
+pub enum Instruction {
     Half { r: Register },
     Triple { r: Register },
     Inc { r: Register },
@@ -85,73 +392,374 @@ enum Instruction {
 }
 
 impl Instruction {
-    fn new(instruction: &str) -> Self {
-        match &instruction[0..3] {
+    fn mnemonic(&self) -> &'static str {
+        match self {
+            Instruction::Half { .. } => "hlf",
+            Instruction::Triple { .. } => "tpl",
+            Instruction::Inc { .. } => "inc",
+            Instruction::Jump { .. } => "jmp",
+            Instruction::JumpIfEven { .. } => "jie",
+            Instruction::JumpIfOne { .. } => "jio",
+        }
+    }
+
+    fn register(&self) -> Option<Register> {
+        match *self {
+            Instruction::Half { r } => Some(r),
+            Instruction::Triple { r } => Some(r),
+            Instruction::Inc { r } => Some(r),
+            Instruction::Jump { .. } => None,
+            Instruction::JumpIfEven { r, .. } => Some(r),
+            Instruction::JumpIfOne { r, .. } => Some(r),
+        }
+    }
+
+    fn offset(&self) -> Option<i32> {
+        match *self {
+            Instruction::Jump { offset } => Some(offset),
+            Instruction::JumpIfEven { offset, .. } => Some(offset),
+            Instruction::JumpIfOne { offset, .. } => Some(offset),
+            _ => None,
+        }
+    }
+
+    /// Assembles a single instruction, operands being separated by whitespace and/or a comma.
+    fn new(instruction: &str) -> Result<Self, ParseError> {
+        let code = code(instruction);
+        let mut tokens = code
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|token| token.is_empty().not());
+        let mut operand = || {
+            tokens.next().ok_or_else(|| {
+                ParseError::new(
+                    Day23::DAY,
+                    instruction,
+                    &code[code.trim_end().len()..],
+                    "missing operand",
+                )
+            })
+        };
+        let register = |token| Register::new(instruction, token);
+        let offset = |token| parse_number(Day23::DAY, instruction, token);
+
+        let mnemonic = operand()?;
+        let parsed = match mnemonic {
             "hlf" => Instruction::Half {
-                r: Register::new(&instruction[4..]),
+                r: register(operand()?)?,
             },
             "tpl" => Instruction::Triple {
-                r: Register::new(&instruction[4..]),
+                r: register(operand()?)?,
             },
             "inc" => Instruction::Inc {
-                r: Register::new(&instruction[4..]),
+                r: register(operand()?)?,
             },
             "jmp" => Instruction::Jump {
-                offset: Self::offset(&instruction[4..]),
+                offset: offset(operand()?)?,
             },
             "jie" => Instruction::JumpIfEven {
-                r: Register::new(&instruction[4..5]),
-                offset: Self::offset(&instruction[7..]),
+                r: register(operand()?)?,
+                offset: offset(operand()?)?,
             },
             "jio" => Instruction::JumpIfOne {
-                r: Register::new(&instruction[4..5]),
-                offset: Self::offset(&instruction[7..]),
+                r: register(operand()?)?,
+                offset: offset(operand()?)?,
             },
-            _ => unreachable!(),
+            _ => {
+                return Err(ParseError::new(
+                    Day23::DAY,
+                    instruction,
+                    mnemonic,
+                    "expected one of 'hlf', 'tpl', 'inc', 'jmp', 'jie' or 'jio'",
+                ))
+            }
+        };
+
+        match operand() {
+            Ok(extra) => Err(ParseError::new(
+                Day23::DAY,
+                instruction,
+                extra,
+                format!("unexpected operand '{extra}'"),
+            )),
+            Err(_) => Ok(parsed),
         }
     }
+}
 
-    fn offset(offset: &str) -> i32 {
-        i32::from_str(offset).unwrap()
+/// Canonical text of the instruction, as found in the puzzle input.
+impl Display for Instruction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match (self.register(), self.offset()) {
+            (Some(r), Some(offset)) => write!(f, "{} {r}, {offset:+}", self.mnemonic()),
+            (Some(r), None) => write!(f, "{} {r}", self.mnemonic()),
+            (None, Some(offset)) => write!(f, "{} {offset:+}", self.mnemonic()),
+            (None, None) => unreachable!(),
+        }
     }
 }
 
+/// Assembles a program of one instruction per line, skipping blank lines and comments.
+pub fn assemble(lines: &[&str]) -> Result<Vec<Instruction>, ParseError> {
+    lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| code(line).trim().is_empty().not())
+        .map(|(idx, line)| Instruction::new(line).map_err(|e| e.on_line(idx + 1)))
+        .collect()
+}
+
+/// The line without its comment, which starts at a `;` or `#`.
+fn code(line: &str) -> &str {
+    line.split([';', '#']).next().unwrap()
+}
+
 #[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
-enum Register {
+pub enum Register {
     A,
     B,
 }
 
 impl Register {
-    fn new(register: &str) -> Self {
+    fn new(instruction: &str, register: &str) -> Result<Self, ParseError> {
         match register {
-            "a" => Register::A,
-            "b" => Register::B,
-            _ => unreachable!(),
+            "a" => Ok(Register::A),
+            "b" => Ok(Register::B),
+            _ => Err(ParseError::new(
+                Day23::DAY,
+                instruction,
+                register,
+                format!("unknown register '{register}'"),
+            )),
+        }
+    }
+}
+
+impl Display for Register {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Register::A => write!(f, "a"),
+            Register::B => write!(f, "b"),
+        }
+    }
+}
+
+/// Lists the program with a label on every jump target, jumps referring to the label
+/// and absolute address of their target instead of a relative offset.
+pub fn disassemble(instructions: &[Instruction]) -> String {
+    let labels = labels(instructions);
+
+    instructions
+        .iter()
+        .enumerate()
+        .flat_map(|(ip, instruction)| {
+            let label = labels.get(&ip).map(|label| format!("{label}:"));
+            label.into_iter().chain([format!(
+                "    {ip:>3}  {}",
+                resolved(ip, instruction, instructions.len(), &labels)
+            )])
+        })
+        .join("\n")
+}
+
+/// Names every instruction that is the target of a jump, in order of address.
+fn labels(instructions: &[Instruction]) -> BTreeMap<usize, String> {
+    instructions
+        .iter()
+        .enumerate()
+        .filter_map(|(ip, instruction)| target(ip, instruction, instructions.len())?)
+        .sorted()
+        .dedup()
+        .enumerate()
+        .map(|(idx, ip)| (ip, format!("L{idx}")))
+        .collect()
+}
+
+/// Where the instruction at `ip` jumps to: `None` for instructions that do not jump,
+/// `Some(None)` for jumps leaving the program.
+fn target(ip: usize, instruction: &Instruction, len: usize) -> Option<Option<usize>> {
+    let offset = instruction.offset()?;
+
+    Some(
+        ip.checked_add_signed(offset as isize)
+            .filter(|&target| target < len),
+    )
+}
+
+/// Text of the instruction at `ip`, jumping to a label instead of an offset.
+fn resolved(
+    ip: usize,
+    instruction: &Instruction,
+    len: usize,
+    labels: &BTreeMap<usize, String>,
+) -> String {
+    let jump = target(ip, instruction, len).map(|target| match target {
+        Some(target) => format!("{} (@{target})", labels[&target]),
+        None => "exit".to_string(),
+    });
+    let operands = instruction
+        .register()
+        .map(|r| r.to_string())
+        .into_iter()
+        .chain(jump)
+        .join(", ");
+
+    format!("{} {operands}", instruction.mnemonic())
+}
+
+/// Where control goes after the last instruction of a [`Block`].
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub enum Successor {
+    /// The block with this index.
+    Block(usize),
+    /// Out of the program, ending it.
+    Exit,
+}
+
+/// Instructions `start..end` that always execute in sequence, only the last one possibly jumping.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Block {
+    pub start: usize,
+    pub end: usize,
+    /// Where a jump leads, if the last instruction jumps (conditionally).
+    pub jump: Option<Successor>,
+    /// Where execution continues when not jumping, absent for unconditional jumps.
+    pub fall_through: Option<Successor>,
+}
+
+/// Basic blocks of a program, connected by the jumps between them.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ControlFlowGraph {
+    instructions: Vec<Instruction>,
+    pub blocks: Vec<Block>,
+}
+
+impl ControlFlowGraph {
+    pub fn new(instructions: &[Instruction]) -> Self {
+        let len = instructions.len();
+        let leaders: BTreeSet<usize> = instructions
+            .iter()
+            .enumerate()
+            .filter_map(|(ip, instruction)| target(ip, instruction, len).map(|t| (ip, t)))
+            .flat_map(|(ip, target)| target.into_iter().chain([ip + 1]))
+            .chain([0])
+            .filter(|&ip| ip < len)
+            .collect();
+        let starts = leaders.iter().copied().collect_vec();
+        let successor = |ip: Option<usize>| match ip {
+            Some(ip) if ip < len => Successor::Block(starts.binary_search(&ip).unwrap()),
+            _ => Successor::Exit,
+        };
+
+        let blocks = starts
+            .iter()
+            .zip(starts.iter().skip(1).copied().chain([len]))
+            .map(|(&start, end)| {
+                let last = &instructions[end - 1];
+                let jump = target(end - 1, last, len);
+
+                Block {
+                    start,
+                    end,
+                    jump: jump.map(successor),
+                    fall_through: match last {
+                        Instruction::Jump { .. } => None,
+                        _ => Some(successor(Some(end))),
+                    },
+                }
+            })
+            .collect();
+
+        Self {
+            instructions: instructions.to_vec(),
+            blocks,
         }
     }
+
+    /// Draws the graph in Graphviz DOT, every block listing its disassembled instructions.
+    pub fn dot(&self) -> String {
+        let labels = labels(&self.instructions);
+        let node = |successor: Successor| match successor {
+            Successor::Block(idx) => format!("b{idx}"),
+            Successor::Exit => "exit".to_string(),
+        };
+
+        let mut dot = vec![
+            "digraph cfg {".to_string(),
+            "    node [shape=box, fontname=monospace];".to_string(),
+            "    exit [shape=doublecircle];".to_string(),
+        ];
+        for (idx, block) in self.blocks.iter().enumerate() {
+            let label = labels
+                .get(&block.start)
+                .map(|label| format!("{label}:\\l"))
+                .unwrap_or_default();
+            let lines = (block.start..block.end)
+                .map(|ip| {
+                    let text =
+                        resolved(ip, &self.instructions[ip], self.instructions.len(), &labels);
+                    format!("{ip:>3}  {text}\\l")
+                })
+                .join("");
+            dot.push(format!("    b{idx} [label=\"{label}{lines}\"];"));
+
+            let conditional = block.jump.is_some() && block.fall_through.is_some();
+            if let Some(jump) = block.jump {
+                let label = if conditional { " [label=\"yes\"]" } else { "" };
+                dot.push(format!("    b{idx} -> {}{label};", node(jump)));
+            }
+            if let Some(fall_through) = block.fall_through {
+                let label = if conditional { " [label=\"no\"]" } else { "" };
+                dot.push(format!("    b{idx} -> {}{label};", node(fall_through)));
+            }
+        }
+
+        dot.push("}".to_string());
+        dot.join("\n")
+    }
+}
+
+pub struct Day23;
+
+impl Solution for Day23 {
+    const DAY: u8 = 23;
+
+    type Input<'a> = Vec<Instruction>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        assemble(&input.lines().collect_vec())
+    }
+
+    fn part_1(input: &Self::Input<'_>) -> Self::Answer1 {
+        solve(input, 0)
+    }
+
+    fn part_2(input: &Self::Input<'_>) -> Self::Answer2 {
+        solve(input, 1)
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use itertools::Itertools;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
 
     use super::*;
+    use crate::util::Input;
 
     #[test]
     fn day_23_part_01_sample() {
         let sample = vec!["inc a", "jio a, +2", "tpl a", "inc a"];
 
-        assert_eq!(0, solve_1(&sample));
+        assert_eq!(Ok(0), solve_1(&sample));
     }
 
     #[test]
     fn day_23_part_01_solution() {
-        let input = include_str!("../../inputs/day_23.txt")
-            .lines()
-            .collect_vec();
+        let input = Input::load(23).unwrap().lines();
 
-        assert_eq!(255, solve_1(&input));
+        assert_eq!(Ok(255), solve_1(&input));
     }
 
     #[test]
@@ -161,10 +769,257 @@ mod tests {
 
     #[test]
     fn day_23_part_02_solution() {
-        let input = include_str!("../../inputs/day_23.txt")
-            .lines()
-            .collect_vec();
+        let input = Input::load(23).unwrap().lines();
+
+        assert_eq!(Ok(334), solve_2(&input));
+    }
+
+    #[test]
+    fn day_23_computer() {
+        let program = assemble(&["inc a", "jio a, +2", "tpl a", "inc a"]).unwrap();
+
+        let mut computer = Computer::new(&program).with_breakpoint(1).with_trace();
+        assert_eq!(Status::Breakpoint { ip: 1 }, computer.run());
+        assert_eq!(1, computer.register(Register::A));
+        assert_eq!(Status::Exited, computer.run());
+        assert_eq!(2, computer.register(Register::A));
+        assert_eq!(
+            vec![(0, [1, 0]), (1, [1, 0]), (3, [2, 0])],
+            computer
+                .trace()
+                .iter()
+                .map(|s| (s.ip, s.registers))
+                .collect_vec()
+        );
+
+        let mut computer = Computer::new(&program).with_register(Register::A, 4);
+        assert_eq!(Status::Ready, computer.step());
+        assert_eq!(Status::Ready, computer.step());
+        assert_eq!((2, 2), (computer.ip(), computer.steps()));
+        assert_eq!(Status::Exited, computer.run());
+        assert_eq!(16, computer.register(Register::A));
+
+        let looping = assemble(&["inc b", "jmp -1"]).unwrap();
+        let mut computer = Computer::new(&looping).with_max_steps(100);
+        assert_eq!(Status::StepLimit { steps: 100 }, computer.run());
+        assert_eq!(50, computer.register(Register::B));
+
+        let overflowing = assemble(&["tpl a"]).unwrap();
+        let mut computer = Computer::new(&overflowing).with_register(Register::A, u64::MAX);
+        assert_eq!(Status::Overflow { ip: 0 }, computer.run());
+    }
+
+    #[test]
+    fn day_23_disassemble() {
+        let collatz = vec![
+            "jio a, +8",
+            "inc b",
+            "jie a, +4",
+            "tpl a",
+            "inc a",
+            "jmp +2",
+            "hlf a",
+            "jmp -7",
+        ];
+        let program = assemble(&collatz).unwrap();
+
+        assert_eq!(
+            [
+                "L0:",
+                "      0  jio a, exit",
+                "      1  inc b",
+                "      2  jie a, L1 (@6)",
+                "      3  tpl a",
+                "      4  inc a",
+                "      5  jmp L2 (@7)",
+                "L1:",
+                "      6  hlf a",
+                "L2:",
+                "      7  jmp L0 (@0)",
+            ]
+            .join("\n"),
+            disassemble(&program)
+        );
+
+        let block = |start, end, jump, fall_through| Block {
+            start,
+            end,
+            jump,
+            fall_through,
+        };
+        assert_eq!(
+            vec![
+                block(0, 1, Some(Successor::Exit), Some(Successor::Block(1))),
+                block(1, 3, Some(Successor::Block(3)), Some(Successor::Block(2))),
+                block(3, 6, Some(Successor::Block(4)), None),
+                block(6, 7, None, Some(Successor::Block(4))),
+                block(7, 8, Some(Successor::Block(0)), None),
+            ],
+            ControlFlowGraph::new(&program).blocks
+        );
+        assert!(ControlFlowGraph::new(&program).dot().contains(
+            "    b1 [label=\"  1  inc b\\l  2  jie a, L1 (@6)\\l\"];\n    \
+                       b1 -> b3 [label=\"yes\"];\n    \
+                       b1 -> b2 [label=\"no\"];"
+        ));
+    }
+
+    #[test]
+    fn day_23_assemble() {
+        let program = vec![
+            "; Collatz step",
+            "  jio   a ,+8   # done",
+            "",
+            "inc b",
+            "jie a,+4",
+            "tpl\ta",
+        ];
+
+        assert_eq!(
+            Ok(vec![
+                Instruction::JumpIfOne {
+                    r: Register::A,
+                    offset: 8
+                },
+                Instruction::Inc { r: Register::B },
+                Instruction::JumpIfEven {
+                    r: Register::A,
+                    offset: 4
+                },
+                Instruction::Triple { r: Register::A },
+            ]),
+            assemble(&program)
+        );
+    }
+
+    #[test]
+    fn day_23_round_trip() {
+        for seed in 0..4 {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let program = (0..1_000)
+                .map(|_| {
+                    let r = [Register::A, Register::B][rng.gen_range(0..2)];
+                    let offset = rng.gen_range(-1_000..=1_000);
+                    match rng.gen_range(0..6) {
+                        0 => Instruction::Half { r },
+                        1 => Instruction::Triple { r },
+                        2 => Instruction::Inc { r },
+                        3 => Instruction::Jump { offset },
+                        4 => Instruction::JumpIfEven { r, offset },
+                        _ => Instruction::JumpIfOne { r, offset },
+                    }
+                })
+                .collect_vec();
+
+            let text = program.iter().map(|i| i.to_string()).collect_vec();
+            assert_eq!(
+                Ok(program.clone()),
+                assemble(&text.iter().map(|l| l.as_str()).collect_vec()),
+                "seed {seed}"
+            );
+
+            let spaced = text
+                .iter()
+                .map(|line| format!("  {} ; {line}", line.replace(' ', "   ")))
+                .collect_vec();
+            assert_eq!(
+                Ok(program),
+                assemble(&spaced.iter().map(|l| l.as_str()).collect_vec()),
+                "seed {seed}"
+            );
+        }
+    }
+
+    #[test]
+    fn day_23_optimisations() {
+        let program = assemble(&[
+            "jio a, +6",
+            "inc a",
+            "inc a",
+            "tpl a",
+            "tpl a",
+            "jmp +4",
+            "tpl a",
+            "inc a",
+            "inc a",
+            "jio a, +8",
+            "inc b",
+            "jie a, +4",
+            "tpl a",
+            "inc a",
+            "jmp +2",
+            "hlf a",
+            "jmp -7",
+        ])
+        .unwrap();
+
+        let r = Register::A;
+        assert_eq!(
+            vec![
+                None,
+                Some(Idiom::Add { r, n: 2 }),
+                None,
+                Some(Idiom::Triple { r, n: 2 }),
+                None,
+                None,
+                None,
+                Some(Idiom::Add { r, n: 2 }),
+                None,
+                Some(Idiom::Collatz {
+                    r,
+                    counter: Register::B,
+                    exit: 8
+                }),
+            ],
+            Idiom::find(&program)[..10]
+        );
+
+        for a in [0, 1, 2, 27, 1_000_000_000_000_000, u64::MAX / 2] {
+            let computer = Computer::new(&program).with_register(Register::A, a);
+
+            assert!(cross_check(computer).is_ok());
+        }
+
+        let computer = Computer::new(&program[9..]).with_max_steps(1_000);
+        let mut computer = cross_check(computer).unwrap();
+        assert_eq!(Status::StepLimit { steps: 1_000 }, computer.run());
+
+        let computer = Computer::new(&program[9..])
+            .with_register(Register::A, 1_000_000_000_000_000)
+            .with_optimisations();
+        let mut limited = computer.clone().with_max_steps(100);
+        assert_eq!(Status::StepLimit { steps: 100 }, limited.run());
+        let mut paused = computer.with_breakpoint(3);
+        assert_eq!(Status::Breakpoint { ip: 3 }, paused.run());
+
+        let computer = Computer::new(&program[9..])
+            .with_register(Register::A, 27)
+            .with_breakpoint(0);
+        assert!(cross_check(computer.clone()).is_ok());
+        let mut computer = computer.with_optimisations();
+        assert_eq!(Status::Breakpoint { ip: 0 }, computer.run());
+        assert_eq!(Status::Breakpoint { ip: 0 }, computer.run());
+        assert_eq!((7, 1), (computer.steps(), computer.register(Register::B)));
+    }
+
+    #[test]
+    fn day_23_parse_error() {
+        let error = solve_1(&["inc a", "jio c, +2"]).unwrap_err();
+
+        assert_eq!((2, 5), (error.line, error.column));
+
+        let error = solve_1(&["inc a", "jmp"]).unwrap_err();
+
+        assert_eq!(2, error.line);
+
+        let error = solve_1(&["inc a", "", "jie a +2 b ; even"]).unwrap_err();
+
+        assert_eq!((3, 10), (error.line, error.column));
+        assert_eq!("unexpected operand 'b'", error.reason);
+
+        let error = solve_1(&["jio a,  # one"]).unwrap_err();
 
-        assert_eq!(334, solve_2(&input));
+        assert_eq!((1, 7), (error.line, error.column));
+        assert_eq!("missing operand", error.reason);
     }
 }
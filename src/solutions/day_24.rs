@@ -1,26 +1,39 @@
 use itertools::Itertools;
+use rustc_hash::FxHashSet;
 use std::ops::Not;
-use std::str::FromStr;
 
-pub fn solve_1(packages: &[&str]) -> u64 {
-    solve(packages, 3)
+use crate::error::{parse_lines, parse_number, ParseError};
+use crate::solutions::Solution;
+
+pub fn solve_1(packages: &[&str]) -> Result<u64, ParseError> {
+    solve(&parse_packages(packages)?, 3)
 }
 
-pub fn solve_2(packages: &[&str]) -> u64 {
-    solve(packages, 4)
+pub fn solve_2(packages: &[&str]) -> Result<u64, ParseError> {
+    solve(&parse_packages(packages)?, 4)
 }
 
-fn solve(packages: &[&str], nr_groups: u64) -> u64 {
-    let packages = packages
-        .iter()
-        .map(|p| u64::from_str(p).unwrap())
-        .collect_vec();
+/// Requires a group weighing `1/nr_groups` of the total weight, without which looking for the smallest
+/// group never ends.
+fn solve(packages: &Packages, nr_groups: u64) -> Result<u64, ParseError> {
+    let weights = &packages.weights;
+    let total = weights.iter().sum::<u64>();
+    if total % nr_groups != 0 || weighs(weights, total / nr_groups).not() {
+        let last = packages.lines.last().copied().unwrap_or_default();
+        return Err(ParseError::new(
+            Day24::DAY,
+            last,
+            &last[last.len()..],
+            format!("no group of packages weighs 1/{nr_groups} of the total weight {total}"),
+        )
+        .on_line(packages.lines.len().max(1)));
+    }
 
-    smallest_groups(&packages, nr_groups)
+    Ok(smallest_groups(weights, nr_groups)
         .iter()
         .map(|g| quantum_entanglement(g))
         .min()
-        .unwrap()
+        .unwrap())
 }
 
 fn smallest_groups(packages: &[u64], nr_groups: u64) -> Vec<Vec<&u64>> {
@@ -41,47 +54,121 @@ fn smallest_groups(packages: &[u64], nr_groups: u64) -> Vec<Vec<&u64>> {
     unreachable!()
 }
 
+/// The weights of the packages, along with their lines to point at in errors.
+pub struct Packages<'a> {
+    lines: Vec<&'a str>,
+    weights: Vec<u64>,
+}
+
+fn parse_packages<'a>(packages: &[&'a str]) -> Result<Packages<'a>, ParseError> {
+    let weights: Vec<u64> = parse_lines(packages, |p| parse_number(Day24::DAY, p, p))?;
+    if weights.is_empty() {
+        return Err(ParseError::new(
+            Day24::DAY,
+            "",
+            "",
+            "expected at least one package",
+        ));
+    }
+
+    Ok(Packages {
+        lines: packages.to_vec(),
+        weights,
+    })
+}
+
+/// Whether some of the packages weigh `weight` together.
+fn weighs(packages: &[u64], weight: u64) -> bool {
+    let mut weights = FxHashSet::from_iter([0]);
+    for &package in packages {
+        let heavier = weights
+            .iter()
+            .map(|w| w + package)
+            .filter(|&w| w <= weight)
+            .collect_vec();
+        weights.extend(heavier);
+    }
+
+    weights.contains(&weight)
+}
+
 fn quantum_entanglement(group: &[&u64]) -> u64 {
     group.iter().copied().product::<u64>()
 }
 
+pub struct Day24;
+
+impl Solution for Day24 {
+    const DAY: u8 = 24;
+
+    type Input<'a> = Packages<'a>;
+    type Answer1 = Result<u64, ParseError>;
+    type Answer2 = Result<u64, ParseError>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_packages(&input.lines().collect_vec())
+    }
+
+    fn part_1(input: &Self::Input<'_>) -> Self::Answer1 {
+        solve(input, 3)
+    }
+
+    fn part_2(input: &Self::Input<'_>) -> Self::Answer2 {
+        solve(input, 4)
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use itertools::Itertools;
-
     use super::*;
+    use crate::util::Input;
 
     #[test]
     fn day_24_part_01_sample() {
         let sample = vec!["1", "2", "3", "4", "5", "7", "8", "9", "10", "11"];
 
-        assert_eq!(99, solve_1(&sample));
+        assert_eq!(Ok(99), solve_1(&sample));
     }
 
     #[ignore = "slow brute force test"]
     #[test]
     fn day_24_part_01_solution() {
-        let input = include_str!("../../inputs/day_24.txt")
-            .lines()
-            .collect_vec();
+        let input = Input::load(24).unwrap().lines();
 
-        assert_eq!(10_439_961_859, solve_1(&input));
+        assert_eq!(Ok(10_439_961_859), solve_1(&input));
     }
 
     #[test]
     fn day_24_part_02_sample() {
         let sample = vec!["1", "2", "3", "4", "5", "7", "8", "9", "10", "11"];
 
-        assert_eq!(44, solve_2(&sample));
+        assert_eq!(Ok(44), solve_2(&sample));
     }
 
     #[ignore = "slow brute force test"]
     #[test]
     fn day_24_part_02_solution() {
-        let input = include_str!("../../inputs/day_24.txt")
-            .lines()
-            .collect_vec();
+        let input = Input::load(24).unwrap().lines();
 
-        assert_eq!(72_050_269, solve_2(&input));
+        assert_eq!(Ok(72_050_269), solve_2(&input));
+    }
+
+    #[test]
+    fn day_24_parse_error() {
+        let error = solve_1(&["1", "2", "-3"]).unwrap_err();
+
+        assert_eq!((3, 1), (error.line, error.column));
+
+        let error = solve_2(&["2", "2", "2"]).unwrap_err();
+        assert_eq!((3, 2), (error.line, error.column));
+        assert_eq!(
+            "no group of packages weighs 1/4 of the total weight 6",
+            error.reason
+        );
+        assert_eq!(Ok(2), solve_1(&["2", "2", "2"]));
+        assert_eq!(Ok(2), solve_2(&["2", "2", "2", "2"]));
+        assert!(solve_1(&["2", "2", "2", "2"]).is_err());
+        assert!(solve_1(&["3", "3", "4"]).is_err());
+        assert!(solve_1(&[]).is_err());
     }
 }
@@ -1,10 +1,14 @@
-use std::str::FromStr;
-
 use regex::Regex;
 
-pub fn solve_1(message: &str) -> u64 {
-    let code = Code::new(message);
-    calculate_nth(index_of(&code))
+use crate::error::{parse_number, ParseError};
+use crate::solutions::Solution;
+
+pub fn solve_1(message: &str) -> Result<u64, ParseError> {
+    Ok(code_at(&Code::new(message)?))
+}
+
+fn code_at(code: &Code) -> u64 {
+    calculate_nth(index_of(code))
 }
 
 pub fn solve_2() {
@@ -33,39 +37,83 @@ fn calculate_nth(n: u64) -> u64 {
 }
 
 #[derive(Debug)]
-struct Code {
+pub struct Code {
     row: u64,
     col: u64,
 }
 
 impl Code {
-    fn new(message: &str) -> Self {
+    fn new(message: &str) -> Result<Self, ParseError> {
         let re = Regex::new(r"To continue, please consult the code grid in the manual\. {2}Enter the code at row (?<row>\d+), column (?<col>\d+)\.").unwrap();
-        let caps = re.captures(message).unwrap();
+        let caps = re.captures(message).ok_or_else(|| {
+            ParseError::new(
+                Day25::DAY,
+                message,
+                message,
+                "expected 'Enter the code at row N, column N.'",
+            )
+        })?;
+        let position = |name| {
+            let at = caps.name(name).unwrap().as_str();
+
+            parse_number(Day25::DAY, message, at).and_then(|n| match n {
+                0 => Err(ParseError::new(
+                    Day25::DAY,
+                    message,
+                    at,
+                    "rows and columns start at 1",
+                )),
+                n => Ok(n),
+            })
+        };
+
+        Ok(Self {
+            row: position("row")?,
+            col: position("col")?,
+        })
+    }
+}
+
+pub struct Day25;
+
+impl Solution for Day25 {
+    const DAY: u8 = 25;
+
+    type Input<'a> = Code;
+    type Answer1 = u64;
+    type Answer2 = &'static str;
 
-        Self {
-            row: u64::from_str(caps.name("row").unwrap().as_str()).unwrap(),
-            col: u64::from_str(caps.name("col").unwrap().as_str()).unwrap(),
-        }
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        Code::new(input.trim())
+    }
+
+    fn part_1(input: &Self::Input<'_>) -> Self::Answer1 {
+        code_at(input)
+    }
+
+    fn part_2(_input: &Self::Input<'_>) -> Self::Answer2 {
+        solve_2();
+        "Snow begins to fall."
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::util::Input;
 
     #[test]
     fn day_25_part_01_sample() {
         let sample = "To continue, please consult the code grid in the manual.  Enter the code at row 4, column 2.";
 
-        assert_eq!(32_451_966, solve_1(sample));
+        assert_eq!(Ok(32_451_966), solve_1(sample));
     }
 
     #[test]
     fn day_25_part_01_solution() {
-        let input = include_str!("../../inputs/day_25.txt").trim();
+        let input = Input::load(25).unwrap().text();
 
-        assert_eq!(2_650_453, solve_1(input));
+        assert_eq!(Ok(2_650_453), solve_1(input));
     }
 
     #[test]
@@ -77,4 +125,11 @@ mod tests {
     fn day_25_part_02_solution() {
         solve_2();
     }
+
+    #[test]
+    fn day_25_parse_error() {
+        let error = solve_1("To continue, please consult the code grid in the manual.  Enter the code at row 0, column 2.").unwrap_err();
+
+        assert_eq!((1, 81), (error.line, error.column));
+    }
 }
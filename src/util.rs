@@ -1,5 +1,16 @@
 #![allow(dead_code)]
 
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use itertools::Itertools;
+use lazy_static::lazy_static;
+use rustc_hash::FxHashMap;
+
+use crate::error::InputError;
+
 pub const BASE_10: u32 = 10;
 
 pub fn lcm(a: u64, b: u64) -> u64 {
@@ -15,3 +26,112 @@ pub fn gcd(a: u64, b: u64) -> u64 {
 
     pair.0
 }
+
+lazy_static! {
+    static ref CACHE: Mutex<FxHashMap<u8, &'static Input>> = Mutex::default();
+}
+
+/// A puzzle input, normalised to `\n` line endings without any trailing whitespace.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Input {
+    day: u8,
+    text: String,
+}
+
+impl Input {
+    pub fn new(day: u8, raw: &str) -> Self {
+        let text = raw
+            .lines()
+            .map(|line| line.trim_end())
+            .join("\n")
+            .trim_end()
+            .to_string();
+
+        Self { day, text }
+    }
+
+    /// Loads the input of `day` from the `inputs` folder, reading each file from disk only once.
+    pub fn load(day: u8) -> Result<&'static Input, InputError> {
+        let mut cache = CACHE.lock().unwrap();
+
+        if let Some(input) = cache.get(&day) {
+            return Ok(input);
+        }
+
+        let input: &'static Input = Box::leak(Box::new(Self::from_path(day, &Self::path(day))?));
+        cache.insert(day, input);
+
+        Ok(input)
+    }
+
+    pub fn from_path(day: u8, path: &Path) -> Result<Self, InputError> {
+        match fs::read_to_string(path) {
+            Ok(raw) => Ok(Self::new(day, &raw)),
+            Err(e) if e.kind() == ErrorKind::NotFound => Err(InputError::Missing {
+                day,
+                path: path.to_path_buf(),
+            }),
+            Err(e) => Err(InputError::Unreadable {
+                day,
+                path: path.to_path_buf(),
+                reason: e.to_string(),
+            }),
+        }
+    }
+
+    pub fn path(day: u8) -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("inputs")
+            .join(format!("day_{:0>2}.txt", day))
+    }
+
+    pub fn day(&self) -> u8 {
+        self.day
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn lines(&self) -> Vec<&str> {
+        self.text.lines().collect()
+    }
+
+    /// Blocks of lines separated by an empty line.
+    pub fn paragraphs(&self) -> Vec<&str> {
+        self.text.split("\n\n").collect()
+    }
+
+    pub fn grid(&self) -> Vec<Vec<char>> {
+        self.text
+            .lines()
+            .map(|line| line.chars().collect())
+            .collect()
+    }
+
+    pub fn chars(&self) -> Vec<char> {
+        self.text.chars().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn input_is_normalised() {
+        let input = Input::new(19, "H => HO\r\nO => HH  \r\n\r\nHOH\r\n\r\n");
+
+        assert_eq!("H => HO\nO => HH\n\nHOH", input.text());
+        assert_eq!(vec!["H => HO", "O => HH", "", "HOH"], input.lines());
+        assert_eq!(vec!["H => HO\nO => HH", "HOH"], input.paragraphs());
+        assert_eq!(vec!['H', 'O', 'H'], input.grid()[3]);
+    }
+
+    #[test]
+    fn missing_input_is_reported() {
+        let error = Input::from_path(7, Path::new("does/not/exist.txt")).unwrap_err();
+
+        assert!(matches!(error, InputError::Missing { day: 7, .. }));
+    }
+}